@@ -0,0 +1,8 @@
+//! Common pieces of the benchmarks of the different proof systems, so their results
+//! can be compared and ingested the same way.
+
+pub mod memory;
+pub mod proof;
+pub mod report;
+pub mod runner;
+pub mod sha256;
@@ -0,0 +1,110 @@
+use std::{
+    collections::BTreeSet,
+    env,
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Parser, Subcommand};
+use nova_bench::runner::{build, execute, parse_d, plan, System};
+
+#[derive(Debug, Parser)]
+#[command(about = "Runs and collects the benchmarks of the different proof systems")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Run the SHA256 chain benchmark of every system over a grid of `k` and `d`, each
+    /// run in a process of its own, into a single results file.
+    Run {
+        #[arg(long, value_enum, value_delimiter = ',', required = true)]
+        systems: Vec<System>,
+
+        /// Numbers of recursive hashes.
+        #[arg(long, value_delimiter = ',', required = true)]
+        k: Vec<usize>,
+
+        /// Hashes per fold, only for Nova. Combinations in which `d` doesn't divide `k`
+        /// are skipped.
+        #[arg(long, value_delimiter = ',', default_value = "10", value_parser = parse_d)]
+        d: Vec<usize>,
+
+        /// File every run appends its record to, CSV if it ends in `.csv`, JSON Lines
+        /// otherwise.
+        #[arg(long, default_value = "results.jsonl")]
+        output: PathBuf,
+
+        /// Root of the repository, with the `nova`, `halo2` and `circom` crates.
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+        root: PathBuf,
+
+        /// Print the runs instead of running them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+fn main() {
+    let Commands::Run {
+        systems,
+        k,
+        d,
+        output,
+        root,
+        dry_run,
+    } = Cli::parse().command;
+
+    let (runs, skipped) = plan(&systems, &k, &d);
+    for reason in &skipped {
+        println!("Skipping {reason}");
+    }
+    if dry_run {
+        for run in &runs {
+            println!("{run}: {}", run.args().join(" "));
+        }
+        return;
+    }
+
+    // The runs don't share a working directory.
+    let output = absolute(&output);
+    let crates = runs
+        .iter()
+        .map(|run| run.system.crate_dir())
+        .collect::<BTreeSet<_>>();
+    for crate_dir in crates {
+        println!("Building {crate_dir}");
+        let built = build(&root, crate_dir)
+            .unwrap_or_else(|e| panic!("failed to run cargo for {crate_dir}: {e}"));
+        assert!(built, "failed to build {crate_dir}");
+    }
+
+    let mut failed = Vec::new();
+    for (i, run) in runs.iter().enumerate() {
+        println!("[{}/{}] {run}", i + 1, runs.len());
+        match execute(&root, run, &output) {
+            Ok(true) => {}
+            Ok(false) => failed.push(run.to_string()),
+            Err(e) => failed.push(format!("{run}: {e}")),
+        }
+    }
+
+    println!("Results are in {}", output.display());
+    if !failed.is_empty() {
+        eprintln!("{} of {} runs failed:", failed.len(), runs.len());
+        for run in &failed {
+            eprintln!("  {run}");
+        }
+        process::exit(1);
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().expect("no current directory").join(path)
+    }
+}
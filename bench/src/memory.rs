@@ -0,0 +1,53 @@
+//! Peak resident set size, read from `/proc/self/status`.
+//!
+//! The kernel keeps the high-water mark in `VmHWM`, and writing `5` to
+//! `/proc/self/clear_refs` resets it to the current RSS, which is what allows
+//! measuring the peak of every phase separately. Both are Linux only, elsewhere the
+//! functions return `None`/`false`. Memory of child processes, such as the Circom
+//! C++ witness generator, isn't included.
+
+use std::fs;
+
+fn status_field(field: &str) -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+/// Peak RSS of the process since it started or since the last [`reset_peak_rss`].
+pub fn peak_rss() -> Option<u64> {
+    status_field("VmHWM")
+}
+
+pub fn current_rss() -> Option<u64> {
+    status_field("VmRSS")
+}
+
+/// Resets the peak RSS to the current RSS, returns whether the kernel allowed it.
+pub fn reset_peak_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn peak_tracks_allocations() {
+        reset_peak_rss();
+        let before = peak_rss().unwrap();
+        let buf = vec![1u8; 64 << 20];
+        let after = peak_rss().unwrap();
+        assert!(after >= before + (32 << 20), "{before} -> {after}");
+        assert!(current_rss().unwrap() <= after);
+        drop(buf);
+    }
+}
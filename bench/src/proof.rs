@@ -0,0 +1,105 @@
+//! Proof directories, written by the benchmarks with `--proof-dir` and read back by
+//! the `verify` binary of each system.
+//!
+//! A proof directory holds [`META_FILE`], describing the run that produced it, the
+//! proof itself in [`PROOF_FILE`] and whatever the verifier needs besides the proof in
+//! [`VK_FILE`]. The encoding of the last two is up to each system.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::BenchRecord;
+
+pub const META_FILE: &str = "meta.json";
+pub const PROOF_FILE: &str = "proof.bin";
+pub const VK_FILE: &str = "vk.bin";
+
+/// Which benchmark run a proof directory comes from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMeta {
+    pub system: String,
+    pub mode: String,
+    pub k: usize,
+    pub d: Option<usize>,
+    pub params: BTreeMap<String, String>,
+}
+
+impl ProofMeta {
+    pub fn from_record(record: &BenchRecord) -> Self {
+        Self {
+            system: record.system.clone(),
+            mode: record.mode.clone(),
+            k: record.k,
+            d: record.d,
+            params: record.params.clone(),
+        }
+    }
+
+    /// Creates `dir` if needed and writes [`META_FILE`] to it.
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self).expect("meta is serializable");
+        fs::write(dir.join(META_FILE), json)
+    }
+
+    pub fn read(dir: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(dir.join(META_FILE))?;
+        serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Empty record for verifying the proof in `dir`: same system, mode and workload as
+    /// the run that produced it, with the proof and verifying key sizes on disk.
+    pub fn verifier_record(&self, dir: &Path) -> BenchRecord {
+        let mut record = BenchRecord::new(&self.system, &self.mode, self.k);
+        record.d = self.d;
+        record.params = self.params.clone();
+        record.set_param("role", "verifier");
+        record.proof_size_bytes = file_size(&proof_path(dir));
+        if let Some(size) = file_size(&vk_path(dir)) {
+            record.set_param("vk_size_bytes", size);
+        }
+        record
+    }
+}
+
+pub fn proof_path(dir: &Path) -> PathBuf {
+    dir.join(PROOF_FILE)
+}
+
+pub fn vk_path(dir: &Path) -> PathBuf {
+    dir.join(VK_FILE)
+}
+
+fn file_size(path: &Path) -> Option<usize> {
+    fs::metadata(path).ok().map(|m| m.len() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("nova-bench-proof-{}", std::process::id()));
+        let mut record = BenchRecord::new("nova", "seq", 100);
+        record.d = Some(10);
+        record.set_param("witness", "cpp");
+
+        let meta = ProofMeta::from_record(&record);
+        meta.write(&dir).unwrap();
+        fs::write(proof_path(&dir), [0u8; 3]).unwrap();
+        assert_eq!(ProofMeta::read(&dir).unwrap(), meta);
+
+        let verifier = meta.verifier_record(&dir);
+        assert_eq!((verifier.k, verifier.d), (100, Some(10)));
+        assert_eq!(verifier.proof_size_bytes, Some(3));
+        assert!(!verifier.params.contains_key("vk_size_bytes"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
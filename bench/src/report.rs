@@ -0,0 +1,352 @@
+//! Machine-readable result records.
+//!
+//! Every benchmark binary fills one [`BenchRecord`] per run and appends it to the
+//! file passed with `--output`: JSON Lines by default, or CSV with one row per phase
+//! when the file ends in `.csv`.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::memory::{peak_rss, reset_peak_rss};
+
+const CSV_HEADER: &str =
+    "system,mode,k,d,constraints,variables,proof_size_bytes,peak_memory_bytes,\
+phase,duration_ns,phase_peak_memory_bytes,params,host,os,arch,cpus,memory_bytes,timestamp";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Phase {
+    pub name: String,
+    pub duration_ns: u128,
+    /// Peak RSS during the phase, see [`crate::memory`]. `None` if it couldn't be
+    /// reset before the phase.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// Distribution of a quantity measured once per step, e.g. the time of each fold.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepStats {
+    pub name: String,
+    pub count: usize,
+    pub min_ns: u128,
+    pub mean_ns: u128,
+    /// Nearest-rank 95th percentile.
+    pub p95_ns: u128,
+    pub total_ns: u128,
+}
+
+impl StepStats {
+    pub fn new(name: &str, durations: &[Duration]) -> Self {
+        let mut ns = durations.iter().map(|d| d.as_nanos()).collect::<Vec<_>>();
+        ns.sort_unstable();
+        let count = ns.len();
+        let total_ns = ns.iter().sum::<u128>();
+        let p95_rank = (count * 95).div_ceil(100).max(1);
+
+        Self {
+            name: name.to_string(),
+            count,
+            min_ns: ns.first().copied().unwrap_or_default(),
+            mean_ns: total_ns.checked_div(count as u128).unwrap_or_default(),
+            p95_ns: ns.get(p95_rank - 1).copied().unwrap_or_default(),
+            total_ns,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub hostname: String,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    /// Total memory of the host, only known on Linux.
+    pub memory_bytes: Option<u64>,
+}
+
+impl HostInfo {
+    pub fn current() -> Self {
+        let hostname = fs::read_to_string("/proc/sys/kernel/hostname")
+            .map(|name| name.trim().to_string())
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_default();
+        let memory_bytes = fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|meminfo| {
+                meminfo
+                    .lines()
+                    .find_map(|line| line.strip_prefix("MemTotal:"))
+                    .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+                    .map(|kb: u64| kb * 1024)
+            });
+
+        Self {
+            hostname,
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            memory_bytes,
+        }
+    }
+}
+
+/// Result of a single benchmark run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    /// Proof system, e.g. `nova`, `halo2` or `groth16`.
+    pub system: String,
+    /// Prover variant, e.g. `seq`/`par` for Nova or `kzg-gwc` for Halo2.
+    pub mode: String,
+    /// Size of the workload, the total number of recursive hashes for SHA256.
+    pub k: usize,
+    /// Number of hashes per fold, if the system folds.
+    pub d: Option<usize>,
+    /// Constraints of the (step) circuit.
+    pub constraints: Option<usize>,
+    /// Variables of the (step) circuit.
+    pub variables: Option<usize>,
+    pub phases: Vec<Phase>,
+    /// Per-step breakdown of the prover, for the systems that fold step by step.
+    pub step_stats: Vec<StepStats>,
+    /// Highest peak RSS over all phases, that of the whole process if it couldn't be
+    /// reset between them.
+    pub peak_memory_bytes: Option<u64>,
+    pub proof_size_bytes: Option<usize>,
+    /// System specific settings, e.g. the witness generator or the SRS size.
+    pub params: BTreeMap<String, String>,
+    pub host: HostInfo,
+    /// Seconds since the Unix epoch at which the record was created.
+    pub timestamp: u64,
+}
+
+impl BenchRecord {
+    pub fn new(system: &str, mode: &str, k: usize) -> Self {
+        Self {
+            system: system.to_string(),
+            mode: mode.to_string(),
+            k,
+            d: None,
+            constraints: None,
+            variables: None,
+            phases: Vec::new(),
+            step_stats: Vec::new(),
+            peak_memory_bytes: None,
+            proof_size_bytes: None,
+            params: BTreeMap::new(),
+            host: HostInfo::current(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |t| t.as_secs()),
+        }
+    }
+
+    pub fn add_phase(&mut self, name: &str, duration: Duration, peak_memory_bytes: Option<u64>) {
+        self.phases.push(Phase {
+            name: name.to_string(),
+            duration_ns: duration.as_nanos(),
+            peak_memory_bytes,
+        });
+        self.peak_memory_bytes = self.peak_memory_bytes.max(peak_memory_bytes);
+    }
+
+    /// Runs `f` and records how long it took and its peak RSS as phase `name`.
+    pub fn time<T>(&mut self, name: &str, f: impl FnOnce() -> T) -> T {
+        let reset = reset_peak_rss();
+        let start = Instant::now();
+        let res = f();
+        let duration = start.elapsed();
+        // Without a reset the high-water mark is that of the whole process so far,
+        // still right for the record but not for the phase.
+        let peak = peak_rss();
+        self.add_phase(name, duration, peak.filter(|_| reset));
+        self.peak_memory_bytes = self.peak_memory_bytes.max(peak);
+        res
+    }
+
+    pub fn add_step_stats(&mut self, name: &str, durations: &[Duration]) {
+        self.step_stats.push(StepStats::new(name, durations));
+    }
+
+    pub fn phase(&self, name: &str) -> Option<Duration> {
+        self.phases
+            .iter()
+            .find(|phase| phase.name == name)
+            .map(|phase| Duration::from_nanos(phase.duration_ns as u64))
+    }
+
+    pub fn set_param(&mut self, key: &str, value: impl ToString) {
+        self.params.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("record is serializable")
+    }
+
+    /// One CSV row per phase, see `CSV_HEADER` for the columns. Step stats are
+    /// flattened into `<name>.min`, `<name>.mean`, `<name>.p95` and `<name>.total` rows.
+    pub fn to_csv_rows(&self) -> Vec<String> {
+        let opt = |x: Option<String>| x.unwrap_or_default();
+        let params = self
+            .params
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(";");
+        let step_phases = self.step_stats.iter().flat_map(|stats| {
+            [
+                ("min", stats.min_ns),
+                ("mean", stats.mean_ns),
+                ("p95", stats.p95_ns),
+                ("total", stats.total_ns),
+            ]
+            .map(|(stat, duration_ns)| Phase {
+                name: format!("{}.{stat}", stats.name),
+                duration_ns,
+                peak_memory_bytes: None,
+            })
+        });
+        self.phases
+            .iter()
+            .cloned()
+            .chain(step_phases)
+            .map(|phase| {
+                [
+                    self.system.clone(),
+                    self.mode.clone(),
+                    self.k.to_string(),
+                    opt(self.d.map(|d| d.to_string())),
+                    opt(self.constraints.map(|c| c.to_string())),
+                    opt(self.variables.map(|v| v.to_string())),
+                    opt(self.proof_size_bytes.map(|s| s.to_string())),
+                    opt(self.peak_memory_bytes.map(|m| m.to_string())),
+                    phase.name,
+                    phase.duration_ns.to_string(),
+                    opt(phase.peak_memory_bytes.map(|m| m.to_string())),
+                    params.clone(),
+                    self.host.hostname.clone(),
+                    self.host.os.clone(),
+                    self.host.arch.clone(),
+                    self.host.cpus.to_string(),
+                    opt(self.host.memory_bytes.map(|m| m.to_string())),
+                    self.timestamp.to_string(),
+                ]
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<_>>()
+                .join(",")
+            })
+            .collect()
+    }
+
+    /// Appends the record to `path`, as CSV if it ends in `.csv` and as a JSON line otherwise.
+    pub fn append_to(&self, path: &Path) -> io::Result<()> {
+        let is_csv = path.extension().is_some_and(|ext| ext == "csv");
+        let is_empty = fs::metadata(path).map_or(true, |m| m.len() == 0);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        if is_csv {
+            if is_empty {
+                writeln!(file, "{CSV_HEADER}")?;
+            }
+            for row in self.to_csv_rows() {
+                writeln!(file, "{row}")?;
+            }
+        } else {
+            writeln!(file, "{}", self.to_json())?;
+        }
+        Ok(())
+    }
+
+    /// Appends the record to `output` if given, and prints it as JSON otherwise.
+    pub fn emit(&self, output: Option<&Path>) {
+        match output {
+            Some(path) => self
+                .append_to(path)
+                .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display())),
+            None => println!("{}", self.to_json()),
+        }
+    }
+}
+
+/// `--output` of the benchmark binaries, `#[command(flatten)]`ed into their arguments.
+#[derive(Clone, Debug, Default, clap::Args)]
+pub struct OutputArgs {
+    /// Append the result record to this file (CSV if it ends in `.csv`, JSON Lines
+    /// otherwise) instead of printing it.
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    /// [`BenchRecord::emit`] to the `--output` file, if any.
+    pub fn emit(&self, record: &BenchRecord) {
+        record.emit(self.output.as_deref())
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_has_one_row_per_phase() {
+        let mut record = BenchRecord::new("nova", "seq", 10);
+        record.d = Some(10);
+        record.set_param("witness", "cpp,wasm");
+        record.add_phase("setup", Duration::from_nanos(1), Some(5));
+        record.add_phase("prove", Duration::from_secs(2), Some(7));
+
+        let rows = record.to_csv_rows();
+        assert_eq!(rows.len(), 2);
+        record.add_step_stats("fold", &[Duration::from_nanos(3)]);
+        let with_steps = record.to_csv_rows();
+        assert_eq!(with_steps.len(), 6);
+        assert!(with_steps[4].contains(",fold.p95,3,,"));
+        assert!(rows[1].starts_with("nova,seq,10,10,,,,7,prove,2000000000,7,\"witness=cpp,wasm\","));
+        assert_eq!(
+            rows[0].split(',').count(),
+            CSV_HEADER.split(',').count() + 1
+        );
+    }
+
+    #[test]
+    fn step_stats() {
+        let durations = (1..=20).rev().map(Duration::from_nanos).collect::<Vec<_>>();
+        let stats = StepStats::new("fold", &durations);
+        assert_eq!(stats.count, 20);
+        assert_eq!(stats.min_ns, 1);
+        assert_eq!(stats.mean_ns, 10);
+        assert_eq!(stats.p95_ns, 19);
+        assert_eq!(stats.total_ns, 210);
+
+        let empty = StepStats::new("fold", &[]);
+        assert_eq!((empty.count, empty.p95_ns, empty.mean_ns), (0, 0, 0));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let mut record = BenchRecord::new("halo2", "kzg-gwc", 1);
+        let answer = record.time("prove", || 42);
+        assert_eq!(answer, 42);
+        record.proof_size_bytes = Some(1024);
+
+        let parsed: BenchRecord = serde_json::from_str(&record.to_json()).unwrap();
+        assert_eq!(parsed, record);
+        assert!(parsed.phase("prove").is_some());
+        assert!(parsed.phase("verify").is_none());
+    }
+}
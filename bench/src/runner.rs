@@ -0,0 +1,240 @@
+//! Runs the benchmarks of all systems over a grid of `k` and `d`, see `nova-bench run`.
+//!
+//! Every run is a separate process, the `sha256` example of the crate of the system,
+//! so that its peak memory isn't inflated by the runs before it. All of them append
+//! their record to the same output file.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A system as benchmarked by the `sha256` example of its crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum System {
+    /// Nova with the sequential prover.
+    Nova,
+    /// Nova with the parallel tree prover.
+    #[value(name = "nova-par")]
+    NovaPar,
+    /// Halo2 with KZG and GWC on BN254.
+    #[value(name = "halo2-kzg")]
+    Halo2Kzg,
+    /// Halo2 with KZG and SHPLONK on BN254.
+    #[value(name = "halo2-shplonk")]
+    Halo2Shplonk,
+    /// Halo2 with IPA on Pasta.
+    #[value(name = "halo2-ipa")]
+    Halo2Ipa,
+    /// Circom with Groth16 on BN254.
+    Groth16,
+}
+
+impl System {
+    pub fn name(&self) -> &'static str {
+        match self {
+            System::Nova => "nova",
+            System::NovaPar => "nova-par",
+            System::Halo2Kzg => "halo2-kzg",
+            System::Halo2Shplonk => "halo2-shplonk",
+            System::Halo2Ipa => "halo2-ipa",
+            System::Groth16 => "groth16",
+        }
+    }
+
+    /// Directory of the crate of the system, relative to the root of the repository.
+    pub fn crate_dir(&self) -> &'static str {
+        match self {
+            System::Nova | System::NovaPar => "nova",
+            System::Halo2Kzg | System::Halo2Shplonk | System::Halo2Ipa => "halo2",
+            System::Groth16 => "circom",
+        }
+    }
+
+    /// Whether the system batches `d` hashes per step. The others prove all `k`
+    /// hashes at once and run once per `k`.
+    pub fn uses_d(&self) -> bool {
+        matches!(self, System::Nova | System::NovaPar)
+    }
+}
+
+/// One benchmark run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub system: System,
+    pub k: usize,
+    pub d: Option<usize>,
+}
+
+impl Run {
+    /// Arguments of the `sha256` example, besides `--output`. Halo2 runs use the
+    /// two-column chip, the one whose proofs bind the digest to the preimage like
+    /// those of Nova and Circom.
+    pub fn args(&self) -> Vec<String> {
+        let k = self.k.to_string();
+        match (self.system, self.d) {
+            (System::Nova | System::NovaPar, Some(d)) => {
+                let mode = if self.system == System::Nova {
+                    "seq"
+                } else {
+                    "par"
+                };
+                vec![
+                    "--mode".to_string(),
+                    mode.to_string(),
+                    "--steps".to_string(),
+                    (self.k / d).to_string(),
+                    "--depth-per-fold".to_string(),
+                    d.to_string(),
+                ]
+            }
+            (System::Halo2Kzg, _) => halo2_args(k, "kzg-gwc"),
+            (System::Halo2Shplonk, _) => halo2_args(k, "kzg-shplonk"),
+            (System::Halo2Ipa, _) => halo2_args(k, "ipa"),
+            (System::Groth16, _) => vec![k],
+            (System::Nova | System::NovaPar, None) => unreachable!("Nova runs need a d"),
+        }
+    }
+}
+
+fn halo2_args(k: String, backend: &str) -> Vec<String> {
+    [k.as_str(), "--backend", backend, "--chip", "two-column"]
+        .map(str::to_string)
+        .to_vec()
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} k={}", self.system.name(), self.k)?;
+        if let Some(d) = self.d {
+            write!(f, " d={d}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every run of `systems` over `ks` and, for the systems that use it, `ds`, along with
+/// the combinations that were skipped and why. `d` has to divide `k`, and can't be 0,
+/// see [`parse_d`].
+pub fn plan(systems: &[System], ks: &[usize], ds: &[usize]) -> (Vec<Run>, Vec<String>) {
+    assert!(!ds.contains(&0), "d can't be 0");
+    let mut runs = Vec::new();
+    let mut skipped = Vec::new();
+    for &system in systems {
+        for &k in ks {
+            if !system.uses_d() {
+                runs.push(Run { system, k, d: None });
+                continue;
+            }
+            for &d in ds {
+                if k % d != 0 {
+                    skipped.push(format!("{} k={k} d={d}: d doesn't divide k", system.name()));
+                } else {
+                    runs.push(Run {
+                        system,
+                        k,
+                        d: Some(d),
+                    });
+                }
+            }
+        }
+    }
+    (runs, skipped)
+}
+
+/// Parses a `d` of `nova-bench run`, which has to be at least 1.
+pub fn parse_d(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("d is the number of hashes per fold, it can't be 0".to_string()),
+        Ok(d) => Ok(d),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Builds the `sha256` example of `crate_dir` in release mode.
+pub fn build(root: &Path, crate_dir: &str) -> io::Result<bool> {
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--example", "sha256"])
+        .current_dir(root.join(crate_dir))
+        .status()?;
+    Ok(status.success())
+}
+
+/// Runs `run` with the example built by [`build`], appending its record to `output`.
+/// The example runs in the directory of its crate, which is where it looks for its
+/// circuits.
+pub fn execute(root: &Path, run: &Run, output: &Path) -> io::Result<bool> {
+    let dir = root.join(run.system.crate_dir());
+    let status = Command::new(example_path(&dir))
+        .args(run.args())
+        .arg("--output")
+        .arg(output)
+        .current_dir(dir)
+        .status()?;
+    Ok(status.success())
+}
+
+fn example_path(crate_dir: &Path) -> PathBuf {
+    crate_dir.join("target/release/examples/sha256")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plans_d_only_for_nova() {
+        let (runs, skipped) = plan(&[System::Nova, System::Halo2Kzg], &[10, 100], &[1, 100]);
+        assert_eq!(
+            runs,
+            vec![
+                Run {
+                    system: System::Nova,
+                    k: 10,
+                    d: Some(1)
+                },
+                Run {
+                    system: System::Nova,
+                    k: 100,
+                    d: Some(1)
+                },
+                Run {
+                    system: System::Nova,
+                    k: 100,
+                    d: Some(100)
+                },
+                Run {
+                    system: System::Halo2Kzg,
+                    k: 10,
+                    d: None
+                },
+                Run {
+                    system: System::Halo2Kzg,
+                    k: 100,
+                    d: None
+                },
+            ]
+        );
+        assert_eq!(skipped, vec!["nova k=10 d=100: d doesn't divide k"]);
+
+        assert_eq!(
+            runs[2].args().join(" "),
+            "--mode seq --steps 1 --depth-per-fold 100"
+        );
+        assert_eq!(
+            runs[3].args().join(" "),
+            "10 --backend kzg-gwc --chip two-column"
+        );
+    }
+
+    #[test]
+    fn rejects_zero_d() {
+        assert_eq!(parse_d("10"), Ok(10));
+        assert_eq!(
+            parse_d("0"),
+            Err("d is the number of hashes per fold, it can't be 0".to_string())
+        );
+        assert!(parse_d("ten").is_err());
+    }
+}
@@ -0,0 +1,48 @@
+//! The SHA256 chain all the benchmarks prove, computed natively to check their output.
+
+use sha2::{Digest, Sha256};
+
+/// Hashes `preimage` `n` times, i.e. what a chain of `n` hashes is expected to output.
+pub fn sha256_chain_digest(preimage: [u8; 32], n: usize) -> [u8; 32] {
+    let mut hash = preimage;
+    for _ in 0..n {
+        hash = Sha256::digest(hash).into();
+    }
+    hash
+}
+
+pub fn bytes_to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn bytes_from_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut bytes = [0; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_of_zero_hashes_is_the_preimage() {
+        assert_eq!(sha256_chain_digest([7; 32], 0), [7; 32]);
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let digest = sha256_chain_digest([0; 32], 1);
+        assert_eq!(
+            bytes_to_hex(&digest),
+            "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925"
+        );
+        assert_eq!(bytes_from_hex(&bytes_to_hex(&digest)), Some(digest));
+        assert_eq!(bytes_from_hex("00"), None);
+    }
+}
@@ -1,6 +1,7 @@
 #!/bin/bash
 
-circom ./circom/sha256_test.circom --r1cs --wasm --sym --c --output ./circom/ --prime vesta
+# snarkjs runs Groth16 on BN254, so compile over its scalar field, not Pasta.
+circom ./circom/sha256_test.circom --r1cs --wasm --sym --c --output ./circom/ --prime bn128
 
 #Doesn't work on M1, using WASM instead
 #cd circom/sha256_test/toy_cpp && make
@@ -0,0 +1,106 @@
+use ark_bn254::{Bn254, Fr};
+use ark_circom::{CircomBuilder, CircomConfig, CircomReduction};
+use ark_groth16::{
+    create_random_proof_with_reduction, generate_random_parameters_with_reduction,
+    prepare_verifying_key, verify_proof,
+};
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::thread_rng;
+use circom::{Prime, R1csHeader, Sha256Chain};
+use clap::Parser;
+use nova_bench::{
+    report::{BenchRecord, OutputArgs},
+    sha256::{bytes_to_hex, sha256_chain_digest},
+};
+
+#[derive(Debug, Parser)]
+#[command(about = "Recursive SHA256 hashing with Circom and Groth16")]
+struct Args {
+    /// Number of recursive hashes.
+    k: usize,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let args = Args::parse();
+    let k = args.k;
+    let chain = Sha256Chain::new(k);
+    chain.compile(Prime::Bn128);
+    chain.compile(Prime::Vesta);
+
+    let mut record = BenchRecord::new("circom", "groth16", k);
+    record.set_param("curve", Prime::Bn128.curve());
+
+    // Same chain as the Nova and Halo2 benchmarks, starting from 0^32.
+    let preimage = [0; 32];
+    let digest = sha256_chain_digest(preimage, k);
+    record.set_param("preimage", bytes_to_hex(&preimage));
+    record.set_param("digest", bytes_to_hex(&digest));
+
+    let cfg = record.time("load", || {
+        CircomConfig::<Bn254>::new(chain.wasm_file(), chain.r1cs_file(Prime::Bn128))
+            .expect("failed to load the circuit")
+    });
+    record.constraints = Some(cfg.r1cs.constraints.len());
+    record.variables = Some(cfg.r1cs.num_variables);
+    println!(
+        "Number of constraints over BN254: {}, variables: {}",
+        cfg.r1cs.constraints.len(),
+        cfg.r1cs.num_variables
+    );
+
+    // The same circuit over Pasta, as Nova would see it. Only its size is reported,
+    // the proof is over BN254.
+    let pasta = R1csHeader::read_file(&chain.r1cs_file(Prime::Vesta))
+        .unwrap_or_else(|e| panic!("failed to read the Pasta R1CS: {e}"));
+    println!(
+        "Number of constraints over Pasta: {}, variables: {}",
+        pasta.num_constraints, pasta.num_wires
+    );
+    record.set_param("pasta_constraints", pasta.num_constraints);
+    record.set_param("pasta_variables", pasta.num_wires);
+
+    let mut builder = CircomBuilder::new(cfg);
+    for byte in preimage {
+        builder.push_input("in", byte);
+    }
+    for byte in digest {
+        builder.push_input("hash", byte);
+    }
+
+    let mut rng = thread_rng();
+    let params = record.time("setup", || {
+        generate_random_parameters_with_reduction::<_, _, _, CircomReduction>(
+            builder.setup(),
+            &mut rng,
+        )
+        .expect("setup should not fail")
+    });
+
+    let circom = record.time("witness", || {
+        builder.build().expect("failed to generate the witness")
+    });
+    // The public signals are the 32 bytes of `out`.
+    let inputs = circom
+        .get_public_inputs()
+        .expect("the witness has no public inputs");
+    assert_eq!(inputs, digest.map(Fr::from).to_vec(), "wrong digest");
+
+    let proof = record.time("prove", || {
+        create_random_proof_with_reduction::<_, _, _, CircomReduction>(circom, &params, &mut rng)
+            .expect("proof generation should not fail")
+    });
+
+    let valid = record.time("verify", || {
+        let pvk = prepare_verifying_key(&params.vk);
+        verify_proof(&pvk, &proof, &inputs).unwrap_or(false)
+    });
+    assert!(valid, "Groth16 proof verification failed");
+
+    record.proof_size_bytes = Some(proof.serialized_size());
+    record.set_param("pk_size_bytes", params.serialized_size());
+    record.set_param("vk_size_bytes", params.vk.serialized_size());
+    args.output.emit(&record);
+}
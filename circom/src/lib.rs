@@ -0,0 +1,253 @@
+//! Recursive SHA256 hashing with Circom and Groth16, proven natively with arkworks.
+//!
+//! `circuits/sha256_test/sha256_test.circom` is rendered for the number of hashes and
+//! compiled with `circom` into `build/k<k>/<prime>`, once per [`Prime`]. Groth16 needs
+//! a pairing, so the circuit is proven over BN254: the R1CS and the wasm witness
+//! generator are loaded by `ark-circom`, which replaces the snarkjs scripts in
+//! `groth16/`. The circuit is also compiled over the Pasta curves, which Nova uses,
+//! only to compare the sizes of both R1CS, see [`R1csHeader`].
+
+use std::{
+    env::current_dir,
+    fs::{self, File},
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const CIRCUIT_NAME: &str = "sha256_test";
+
+/// Field the circuit is compiled over, the `--prime` of `circom`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prime {
+    /// Scalar field of BN254, the one Groth16 is run on.
+    Bn128,
+    /// Scalar field of Pallas, the one of the primary circuit of Nova. `circom` names
+    /// primes after the curve they are the base field of, so this is its `vesta`.
+    Vesta,
+}
+
+impl Prime {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Prime::Bn128 => "bn128",
+            Prime::Vesta => "vesta",
+        }
+    }
+
+    /// Curve of the result records.
+    pub fn curve(&self) -> &'static str {
+        match self {
+            Prime::Bn128 => "bn254",
+            Prime::Vesta => "pasta",
+        }
+    }
+}
+
+/// `Main(k)` of `sha256_test.circom`: `k` recursive hashes of `in`, whose output is
+/// also checked against the `hash` input.
+pub struct Sha256Chain {
+    pub num_hashes: usize,
+}
+
+impl Sha256Chain {
+    pub fn new(num_hashes: usize) -> Self {
+        Self { num_hashes }
+    }
+
+    /// Renders and compiles `Main(num_hashes)` over `prime` unless it was already
+    /// built. The wasm witness generator is only built for [`Prime::Bn128`].
+    pub fn compile(&self, prime: Prime) {
+        let built = match prime {
+            Prime::Bn128 => self.r1cs_file(prime).exists() && self.wasm_file().exists(),
+            Prime::Vesta => self.r1cs_file(prime).exists(),
+        };
+        if built {
+            return;
+        }
+        println!(
+            "Compiling the circuit for k = {} over {}",
+            self.num_hashes,
+            prime.name()
+        );
+        let build_dir = self.build_dir(prime);
+        fs::create_dir_all(&build_dir).expect("failed to create the build directory");
+
+        let source = circuits_dir().join(CIRCUIT_NAME).join("sha256_test.circom");
+        let source = fs::read_to_string(&source)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", source.display()));
+        let rendered = build_dir.join(format!("{CIRCUIT_NAME}.circom"));
+        fs::write(&rendered, render_main(&source, self.num_hashes))
+            .unwrap_or_else(|e| panic!("failed to write {}: {e}", rendered.display()));
+
+        // `-l` resolves the includes relative to the original circuit.
+        let mut command = Command::new("circom");
+        command
+            .arg(&rendered)
+            .arg("-l")
+            .arg(circuits_dir().join(CIRCUIT_NAME))
+            .args(["--prime", prime.name(), "--r1cs", "--sym"]);
+        if prime == Prime::Bn128 {
+            command.arg("--wasm");
+        }
+        let status = command
+            .arg("--output")
+            .arg(&build_dir)
+            .status()
+            .expect("failed to execute circom");
+        assert!(status.success(), "failed to compile the circuit");
+    }
+
+    pub fn r1cs_file(&self, prime: Prime) -> PathBuf {
+        self.build_dir(prime).join(format!("{CIRCUIT_NAME}.r1cs"))
+    }
+
+    /// Witness generator of the [`Prime::Bn128`] circuit.
+    pub fn wasm_file(&self) -> PathBuf {
+        self.build_dir(Prime::Bn128)
+            .join(format!("{CIRCUIT_NAME}_js"))
+            .join(format!("{CIRCUIT_NAME}.wasm"))
+    }
+
+    fn build_dir(&self, prime: Prime) -> PathBuf {
+        current_dir()
+            .unwrap()
+            .join("build")
+            .join(format!("k{}", self.num_hashes))
+            .join(prime.name())
+    }
+}
+
+/// Header of an `.r1cs` file, enough to tell the size of the circuit without loading
+/// its constraints.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1csHeader {
+    /// Little-endian modulus of the field.
+    pub prime: Vec<u8>,
+    pub num_wires: u32,
+    pub num_public_outputs: u32,
+    pub num_public_inputs: u32,
+    pub num_private_inputs: u32,
+    pub num_constraints: u32,
+}
+
+impl R1csHeader {
+    pub fn read_file(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        Self::read(BufReader::new(file))
+    }
+
+    /// Reads the header section, skipping the sections before it.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != b"r1cs" {
+            return Err(invalid("not an r1cs file"));
+        }
+        let _version = read_u32(&mut reader)?;
+        let num_sections = read_u32(&mut reader)?;
+        for _ in 0..num_sections {
+            let section_type = read_u32(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+            if section_type != 1 {
+                io::copy(&mut (&mut reader).take(size), &mut io::sink())?;
+                continue;
+            }
+            let field_size = read_u32(&mut reader)?;
+            let mut prime = vec![0; field_size as usize];
+            reader.read_exact(&mut prime)?;
+            let num_wires = read_u32(&mut reader)?;
+            let num_public_outputs = read_u32(&mut reader)?;
+            let num_public_inputs = read_u32(&mut reader)?;
+            let num_private_inputs = read_u32(&mut reader)?;
+            let _num_labels = read_u64(&mut reader)?;
+            let num_constraints = read_u32(&mut reader)?;
+            return Ok(Self {
+                prime,
+                num_wires,
+                num_public_outputs,
+                num_public_inputs,
+                num_private_inputs,
+                num_constraints,
+            });
+        }
+        Err(invalid("the r1cs file has no header section"))
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn circuits_dir() -> PathBuf {
+    current_dir().unwrap().join("circuits")
+}
+
+/// Replaces the argument of `component main = Main(..)` with `num_hashes`.
+fn render_main(source: &str, num_hashes: usize) -> String {
+    const MAIN: &str = "component main = Main(";
+    let start = source
+        .find(MAIN)
+        .expect("the circuit has no main component")
+        + MAIN.len();
+    let end = start + source[start..].find(')').expect("unclosed main component");
+    format!("{}{num_hashes}{}", &source[..start], &source[end..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_r1cs_header() {
+        let mut file = b"r1cs".to_vec();
+        file.extend(1u32.to_le_bytes());
+        file.extend(2u32.to_le_bytes());
+        // A section before the header is skipped.
+        file.extend(2u32.to_le_bytes());
+        file.extend(3u64.to_le_bytes());
+        file.extend([0xff; 3]);
+        file.extend(1u32.to_le_bytes());
+        file.extend(40u64.to_le_bytes());
+        file.extend(4u32.to_le_bytes());
+        file.extend([7, 0, 0, 0]);
+        for n in [100u32, 32, 0, 64] {
+            file.extend(n.to_le_bytes());
+        }
+        file.extend(99u64.to_le_bytes());
+        file.extend(50u32.to_le_bytes());
+
+        let header = R1csHeader::read(file.as_slice()).unwrap();
+        assert_eq!(
+            header,
+            R1csHeader {
+                prime: vec![7, 0, 0, 0],
+                num_wires: 100,
+                num_public_outputs: 32,
+                num_public_inputs: 0,
+                num_private_inputs: 64,
+                num_constraints: 50,
+            }
+        );
+        assert!(R1csHeader::read(&b"wtns"[..]).is_err());
+    }
+
+    #[test]
+    fn renders_main() {
+        let source = "template Main(depth) {}\n\ncomponent main = Main(10);";
+        assert_eq!(
+            render_main(source, 1000),
+            "template Main(depth) {}\n\ncomponent main = Main(1000);"
+        );
+    }
+}
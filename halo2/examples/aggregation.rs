@@ -0,0 +1,166 @@
+use std::{path::PathBuf, time::Instant};
+
+use clap::Parser;
+use halo2::{
+    aggregation::{
+        chain_public_inputs, prove_aggregation, prove_chunk, verify_aggregation, verify_chunk,
+        AggregationCircuit,
+    },
+    backend::KzgGwc,
+    circuit::{chain_instance, ChainHasher, Sha256Chain, TwoColumn},
+    params::{circuit_size, read_srs, write_srs},
+};
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr},
+    plonk::{keygen_pk, keygen_vk},
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+};
+use nova_bench::{
+    report::{BenchRecord, OutputArgs},
+    sha256::{bytes_to_hex, sha256_chain_digest},
+};
+use rand::rngs::OsRng;
+
+#[derive(Debug, Parser)]
+#[command(about = "Recursive SHA256 hashing with Halo2, aggregating the proofs of chunks")]
+struct Args {
+    /// Number of recursive hashes.
+    k: usize,
+
+    /// Number of hashes proven by each chunk proof, has to divide `k`.
+    #[arg(long, default_value_t = 1)]
+    depth_per_proof: usize,
+
+    /// log2 of the SRS size. The aggregation circuit uses all of it, the chunk proofs a
+    /// downsized copy.
+    #[arg(long, default_value_t = 23)]
+    srs_k: u32,
+
+    /// Read the SRS from this file, downsized to `srs_k`. If the file doesn't exist, a
+    /// new SRS is generated and written to it.
+    #[arg(long)]
+    srs: Option<PathBuf>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let args = Args::parse();
+    let (k, d) = (args.k, args.depth_per_proof);
+    assert!(
+        d > 0 && k % d == 0,
+        "--depth-per-proof has to divide the number of hashes"
+    );
+    let num_chunks = k / d;
+
+    let mut record = BenchRecord::new("halo2", "kzg-gwc-aggregation", k);
+    record.d = Some(d);
+    record.set_param("curve", "bn254");
+    // Table16 doesn't constrain the ends of a chunk, which the aggregation links up.
+    record.set_param("chip", <TwoColumn as ChainHasher<Fr>>::NAME);
+    record.set_param("sound", <TwoColumn as ChainHasher<Fr>>::SOUND);
+    record.set_param("num_chunks", num_chunks);
+
+    // Same chain as the other benchmarks, starting from 0^32, cut every `d` hashes.
+    let mut links = vec![[0; 32]];
+    for i in 0..num_chunks {
+        links.push(sha256_chain_digest(links[i], d));
+    }
+    record.set_param("preimage", bytes_to_hex(&links[0]));
+    record.set_param("digest", bytes_to_hex(&links[num_chunks]));
+
+    let params = match &args.srs {
+        Some(path) if path.exists() => {
+            record.set_param("srs", path.display());
+            record
+                .time("srs_load", || read_srs::<KzgGwc>(path, args.srs_k))
+                .unwrap_or_else(|e| panic!("failed to read the SRS {}: {e}", path.display()))
+        }
+        srs => {
+            record.set_param("srs", "generated");
+            let params = record.time("setup", || ParamsKZG::<Bn256>::setup(args.srs_k, OsRng));
+            if let Some(path) = srs {
+                write_srs::<KzgGwc>(path, &params)
+                    .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+            }
+            params
+        }
+    };
+    record.set_param("srs_k", args.srs_k);
+
+    let chunk_size = circuit_size::<Fr, _>(&Sha256Chain::<TwoColumn>::new([0; 32], d));
+    let mut chunk_params = params.clone();
+    chunk_params.downsize(chunk_size.min_k);
+    record.set_param("chunk_rows", chunk_size.rows);
+    record.set_param("chunk_srs_k", chunk_size.min_k);
+
+    let chunk_pk = record.time("chunk_keygen", || {
+        let circuit = Sha256Chain::<TwoColumn>::new([0; 32], d);
+        let vk = keygen_vk(&chunk_params, &circuit).expect("keygen_vk should not fail");
+        keygen_pk(&chunk_params, vk, &circuit).expect("keygen_pk should not fail")
+    });
+
+    let mut chunk_times = Vec::with_capacity(num_chunks);
+    let snarks = record.time("chunk_prove", || {
+        links
+            .windows(2)
+            .map(|link| {
+                let start = Instant::now();
+                let instance = chain_instance::<Fr>(&link[0], &link[1]);
+                let snark = prove_chunk(
+                    &chunk_params,
+                    &chunk_pk,
+                    Sha256Chain::new(link[0], d),
+                    instance,
+                );
+                chunk_times.push(start.elapsed());
+                snark
+            })
+            .collect::<Vec<_>>()
+    });
+    record.add_step_stats("chunk_prove", &chunk_times);
+    record.set_param("chunk_proof_size_bytes", snarks[0].proof().len());
+    assert!(
+        snarks
+            .iter()
+            .all(|snark| verify_chunk(&chunk_params, chunk_pk.get_vk(), snark)),
+        "chunk proof verification failed"
+    );
+
+    let circuit = record.time("accumulate", || AggregationCircuit::new(&params, snarks));
+    let size = circuit_size::<Fr, _>(&circuit);
+    assert!(
+        size.min_k <= args.srs_k,
+        "the aggregation circuit needs an SRS of at least 2^{}",
+        size.min_k
+    );
+    println!(
+        "Aggregation circuit: {} rows (+{} reserved), using an SRS of 2^{}",
+        size.rows, size.reserved_rows, args.srs_k
+    );
+    record.set_param("rows", size.rows);
+    record.set_param("min_srs_k", size.min_k);
+
+    let pk = record.time("aggregation_keygen", || {
+        let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+        keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail")
+    });
+
+    let instances = circuit.instances();
+    assert_eq!(
+        chain_public_inputs(&instances),
+        chain_instance::<Fr>(&links[0], &links[num_chunks])
+    );
+    let proof = record.time("aggregation_prove", || {
+        prove_aggregation(&params, &pk, circuit)
+    });
+
+    let valid = record.time("verify", || {
+        verify_aggregation(&params, pk.get_vk(), &proof, &instances)
+    });
+    assert!(valid, "aggregation proof verification failed");
+
+    record.proof_size_bytes = Some(proof.len());
+    args.output.emit(&record);
+}
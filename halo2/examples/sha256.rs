@@ -1,100 +1,241 @@
+use std::path::{Path, PathBuf};
+
 use ark_std::{end_timer, start_timer};
-use halo2_gadgets::sha256::{BlockWord, Sha256, Table16Chip, Table16Config, BLOCK_SIZE};
-use halo2_proofs::halo2curves::bn256::{Bn256, Fr};
-use halo2_proofs::{
-    circuit::{Layouter, SimpleFloorPlanner, Value},
-    plonk::{create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, ConstraintSystem, Error},
-    poly::commitment::Params,
-    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+use clap::Parser;
+use halo2::{
+    backend::{Backend, Halo2Backend, Ipa, KzgGwc, KzgShplonk},
+    circuit::{chain_instance, ChainHasher, Chip, Sha256Chain, Table16, TwoColumn},
+    params::{circuit_stats, read_srs, write_srs},
+    proof,
 };
-use rand::rngs::OsRng;
-
 use halo2_proofs::{
-    poly::{
-        commitment::ParamsProver,
-        kzg::{
-            commitment::{KZGCommitmentScheme, ParamsKZG},
-            multiopen::{ProverGWC, VerifierGWC},
-            strategy::AccumulatorStrategy,
-        },
-    },
-    transcript::{TranscriptReadBuffer, TranscriptWriterBuffer},
+    arithmetic::FieldExt,
+    dev::{CircuitLayout, MockProver},
+    plonk::Circuit,
+};
+use nova_bench::{
+    report::{BenchRecord, OutputArgs},
+    sha256::{bytes_to_hex, sha256_chain_digest},
 };
+use plotters::prelude::*;
 
-// Todo: Allow to pass an input and constrain correctness against a PI or similar.
-#[derive(Default)]
-struct MyCircuit {
-    iter_num: usize,
-}
+/// Size in pixels of the `--layout` image. The rows are scaled to its height, so small
+/// regions end up thinner than a pixel for long chains.
+const LAYOUT_SIZE: (u32, u32) = (1024, 4096);
+/// Largest chain `--layout` renders. Beyond it the image is mostly the same hash
+/// repeated, with hundreds of rows to a pixel, and laying it out takes long.
+const MAX_LAYOUT_HASHES: usize = 4;
+const MAX_LAYOUT_ROWS: usize = 1 << 18;
 
-impl Circuit<Fr> for MyCircuit {
-    type Config = Table16Config;
-    type FloorPlanner = SimpleFloorPlanner;
+#[derive(Debug, Parser)]
+#[command(about = "Recursive SHA256 hashing with Halo2")]
+struct Args {
+    /// Number of recursive hashes.
+    k: usize,
 
-    fn without_witnesses(&self) -> Self {
-        Self::default()
-    }
+    /// Commitment scheme and multiopen: KZG on BN254 with GWC or SHPLONK, or IPA on the
+    /// Pasta curves like Nova.
+    #[arg(long, value_enum, default_value_t = Backend::KzgGwc)]
+    backend: Backend,
 
-    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        Table16Chip::configure(meta)
-    }
+    /// SHA256 chip: two advice columns without lookups, closer to R1CS, or the lookup
+    /// based Table16 of `halo2_gadgets`, whose proofs don't bind the digest.
+    #[arg(long, value_enum, default_value_t = Chip::TwoColumn)]
+    chip: Chip,
 
-    fn synthesize(
-        &self,
-        config: Self::Config,
-        mut layouter: impl Layouter<Fr>,
-    ) -> Result<(), Error> {
-        Table16Chip::load(config.clone(), &mut layouter)?;
-        let table16_chip = Table16Chip::construct(config);
-
-        let mut test_input = [BlockWord(Value::known(0xff)); 8];
-
-        for _ in 0..self.iter_num {
-            test_input = Sha256::digest(
-                table16_chip.clone(),
-                layouter.namespace(|| "'abc' * 2"),
-                &test_input,
-            )?
-            .0;
-        }
-        Ok(())
-    }
+    /// log2 of the SRS size, the smallest one that fits the circuit if not given.
+    #[arg(long)]
+    srs_k: Option<u32>,
+
+    /// Read the SRS from this file, downsized to `srs_k`. If the file doesn't exist, a
+    /// new SRS is generated and written to it.
+    #[arg(long)]
+    srs: Option<PathBuf>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    /// Render the floor plan of the circuit to this PNG file, see `LAYOUT_SIZE`. Only
+    /// for short chains, see `MAX_LAYOUT_HASHES`.
+    #[arg(long)]
+    layout: Option<PathBuf>,
+
+    /// Only check the circuit with `MockProver` at `srs_k` and report its shape, instead
+    /// of proving it.
+    #[arg(long)]
+    mock: bool,
+
+    /// Write the proof, verifying key and SRS to this directory, see `examples/verify.rs`.
+    #[arg(long)]
+    proof_dir: Option<PathBuf>,
 }
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let k: usize = args[2].parse().unwrap();
-    let params_size: u32 = args[1].parse().unwrap();
+    let args = Args::parse();
+    let record = match (args.backend, args.chip) {
+        (Backend::KzgGwc, Chip::Table16) => bench::<KzgGwc, Table16>(&args),
+        (Backend::KzgGwc, Chip::TwoColumn) => bench::<KzgGwc, TwoColumn>(&args),
+        (Backend::KzgShplonk, Chip::Table16) => bench::<KzgShplonk, Table16>(&args),
+        (Backend::KzgShplonk, Chip::TwoColumn) => bench::<KzgShplonk, TwoColumn>(&args),
+        (Backend::Ipa, Chip::Table16) => bench::<Ipa, Table16>(&args),
+        (Backend::Ipa, Chip::TwoColumn) => bench::<Ipa, TwoColumn>(&args),
+    };
+    args.output.emit(&record);
+}
+
+fn bench<B: Halo2Backend, H: ChainHasher<B::Scalar>>(args: &Args) -> BenchRecord {
+    let k = args.k;
+    let mut record = BenchRecord::new("halo2", B::NAME, k);
+    record.set_param("curve", B::CURVE);
+    record.set_param("chip", H::NAME);
+    record.set_param("sound", H::SOUND);
+    if !H::SOUND {
+        eprintln!(
+            "Warning: {} doesn't constrain the public preimage and digest to the chain, \
+             the proof doesn't show that digest = h^k(preimage), see `--chip two-column`",
+            H::NAME
+        );
+    }
+
+    // Same chain as the Nova and Circom benchmarks, starting from 0^32.
+    let preimage = [0; 32];
+    let circuit = Sha256Chain::<H>::new(preimage, k);
+    let digest = sha256_chain_digest(preimage, k);
+    let instance = chain_instance::<B::Scalar>(&preimage, &digest);
+    record.set_param("preimage", bytes_to_hex(&preimage));
+    record.set_param("digest", bytes_to_hex(&digest));
 
-    let params = ParamsKZG::<Bn256>::setup(params_size, OsRng);
-    let circuit = MyCircuit { iter_num: k };
+    let size = B::circuit_size(&circuit);
+    let params_size = args.srs_k.unwrap_or(size.min_k);
+    assert!(
+        params_size >= size.min_k,
+        "the circuit needs an SRS of at least 2^{}",
+        size.min_k
+    );
+    println!(
+        "{} rows (+{} reserved), using an SRS of 2^{params_size}",
+        size.rows, size.reserved_rows
+    );
+    record.set_param("rows", size.rows);
+    record.set_param("min_srs_k", size.min_k);
+    record.set_param("srs_k", params_size);
+
+    if let Some(path) = &args.layout {
+        assert!(
+            k <= MAX_LAYOUT_HASHES && size.rows <= MAX_LAYOUT_ROWS,
+            "--layout renders at most {MAX_LAYOUT_HASHES} hashes and {MAX_LAYOUT_ROWS} rows, \
+             this circuit has {k} hashes and {} rows",
+            size.rows
+        );
+        let title = format!("SHA256 chain, {k} hashes, {}", H::NAME);
+        render_layout(
+            path,
+            &title,
+            params_size,
+            &circuit,
+            size.rows + size.reserved_rows,
+        );
+        println!("Wrote the layout to {}", path.display());
+    }
 
-    // Plotting circuit
-    // use plotters::prelude::*;
-    // let root = BitMapBackend::new("sha_layout.png", (1024, 7680)).into_drawing_area();
-    // root.fill(&WHITE).unwrap();
-    // let root = root
-    //     .titled(&format!("SHA - Depth={}", params_size), ("sans-serif", 60))
-    //     .unwrap();
+    if args.mock {
+        mock(&circuit, instance, params_size, &mut record);
+        return record;
+    }
 
-    // halo2_proofs::dev::CircuitLayout::default()
-    //     .render(params_size as u32, &circuit, &root)
-    //     .unwrap();
+    let params = match &args.srs {
+        Some(path) if path.exists() => {
+            record.set_param("srs", path.display());
+            record
+                .time("srs_load", || read_srs::<B>(path, params_size))
+                .unwrap_or_else(|e| panic!("failed to read the SRS {}: {e}", path.display()))
+        }
+        srs => {
+            record.set_param("srs", "generated");
+            let params = record.time("setup", || B::setup(params_size));
+            if let Some(path) = srs {
+                write_srs::<B>(path, &params)
+                    .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+            }
+            params
+        }
+    };
 
-    let vk = keygen_vk(&params, &circuit).unwrap();
-    let pk = keygen_pk(&params, vk, &circuit).unwrap();
+    let pk = record.time("keygen", || B::keygen(&params, &circuit));
 
     let start = start_timer!(|| "Compute Halo2 recursive hash");
-    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
-    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
-        &params,
-        &pk,
-        &[circuit],
-        &[],
-        OsRng,
-        &mut transcript,
-    )
-    .expect("proof generation should not fail");
-    let proof: Vec<u8> = transcript.finalize();
+    let proof = record.time("prove", || B::prove(&params, &pk, circuit, &instance));
+    end_timer!(start);
+
+    let start = start_timer!(|| "Verify Halo2 proof");
+    let valid = record.time("verify", || {
+        B::verify(&params, pk.get_vk(), &proof, &instance)
+    });
     end_timer!(start);
+    assert!(valid, "Halo2 proof verification failed");
+
+    record.proof_size_bytes = Some(proof.len());
+    if let Some(dir) = &args.proof_dir {
+        proof::write::<B>(dir, &params, pk.get_vk(), &proof, &record)
+            .unwrap_or_else(|e| panic!("failed to write the proof to {}: {e}", dir.display()));
+        println!("Wrote the proof to {}", dir.display());
+    }
+    record
+}
+
+/// Renders the floor plan of `circuit` on `2^k` rows to `path`, cropped to the first
+/// `rows`, i.e. those in use.
+fn render_layout<F: FieldExt, C: Circuit<F>>(
+    path: &Path,
+    title: &str,
+    k: u32,
+    circuit: &C,
+    rows: usize,
+) {
+    let root = BitMapBackend::new(path, LAYOUT_SIZE).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+    let root = root.titled(title, ("sans-serif", 40)).unwrap();
+    CircuitLayout::default()
+        .view_height(0..rows.min(1 << k))
+        .render(k, circuit, &root)
+        .unwrap_or_else(|e| panic!("failed to render the layout: {e}"));
+    root.present()
+        .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+}
+
+/// Runs `MockProver` on `circuit` with an SRS of `2^k` and reports the shape of the
+/// circuit, the Halo2 counterpart of the constraint counts Nova prints.
+fn mock<F: FieldExt, C: Circuit<F>>(
+    circuit: &C,
+    instance: Vec<F>,
+    k: u32,
+    record: &mut BenchRecord,
+) {
+    let stats = circuit_stats(circuit);
+    println!("Advice columns: {}", stats.advice_columns);
+    println!("Fixed columns: {}", stats.fixed_columns);
+    println!("Instance columns: {}", stats.instance_columns);
+    println!("Selectors: {}", stats.selectors);
+    println!("Columns with equality: {}", stats.permutation_columns);
+    println!("Gates: {} ({} constraints)", stats.gates, stats.constraints);
+    println!("Lookups: {}", stats.lookups);
+    println!("Degree: {}", stats.degree);
+    println!(
+        "Rows: {} of 2^{k} (+{} reserved)",
+        stats.size.rows, stats.size.reserved_rows
+    );
+    record.set_param("advice_columns", stats.advice_columns);
+    record.set_param("fixed_columns", stats.fixed_columns);
+    record.set_param("selectors", stats.selectors);
+    record.set_param("gates", stats.gates);
+    record.set_param("lookups", stats.lookups);
+    record.set_param("degree", stats.degree);
+    record.set_param("gate_constraints", stats.constraints);
+
+    record.time("mock", || {
+        MockProver::run(k, circuit, vec![instance])
+            .expect("failed to run the circuit")
+            .assert_satisfied()
+    });
+    println!("The circuit is satisfied");
 }
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use halo2::{
+    backend::{Backend, Halo2Backend, Ipa, KzgGwc, KzgShplonk},
+    circuit::{chain_instance, ChainHasher, Chip, Sha256Chain, Table16, TwoColumn},
+    proof,
+};
+use nova_bench::{
+    proof::ProofMeta,
+    report::{BenchRecord, OutputArgs},
+    sha256::{bytes_to_hex, sha256_chain_digest},
+};
+
+#[derive(Debug, Parser)]
+#[command(about = "Verify a Halo2 proof written with --proof-dir")]
+struct Args {
+    /// Directory the proof was written to.
+    proof_dir: PathBuf,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let args = Args::parse();
+    let dir = &args.proof_dir;
+
+    let meta = ProofMeta::read(dir)
+        .unwrap_or_else(|e| panic!("failed to read the metadata in {}: {e}", dir.display()));
+    let mut record = meta.verifier_record(dir);
+    // The public inputs, the start and the end of the chain, are computed here rather
+    // than taken from the metadata, so that only a proof of `k` hashes of 0^32 passes.
+    let preimage = [0; 32];
+    let digest = sha256_chain_digest(preimage, meta.k);
+    if let Some(claimed) = meta.params.get("digest") {
+        assert_eq!(
+            claimed,
+            &bytes_to_hex(&digest),
+            "the metadata claims another digest for {} recursive hashes",
+            meta.k
+        );
+    }
+
+    let backend = Backend::from_name(&meta.mode)
+        .unwrap_or_else(|| panic!("unknown Halo2 backend {}", meta.mode));
+    let chip = match meta.params.get("chip") {
+        // Proofs written before the chip could be picked are Table16 ones.
+        None => Chip::Table16,
+        Some(name) => Chip::from_name(name).unwrap_or_else(|| panic!("unknown SHA256 chip {name}")),
+    };
+    record.set_param("sound", chip.sound());
+    if !chip.sound() {
+        eprintln!(
+            "Warning: {} proofs don't show that the digest is that of the preimage",
+            chip.name()
+        );
+    }
+    let valid = match (backend, chip) {
+        (Backend::KzgGwc, Chip::Table16) => {
+            verify::<KzgGwc, Table16>(dir, &preimage, &digest, &mut record)
+        }
+        (Backend::KzgGwc, Chip::TwoColumn) => {
+            verify::<KzgGwc, TwoColumn>(dir, &preimage, &digest, &mut record)
+        }
+        (Backend::KzgShplonk, Chip::Table16) => {
+            verify::<KzgShplonk, Table16>(dir, &preimage, &digest, &mut record)
+        }
+        (Backend::KzgShplonk, Chip::TwoColumn) => {
+            verify::<KzgShplonk, TwoColumn>(dir, &preimage, &digest, &mut record)
+        }
+        (Backend::Ipa, Chip::Table16) => {
+            verify::<Ipa, Table16>(dir, &preimage, &digest, &mut record)
+        }
+        (Backend::Ipa, Chip::TwoColumn) => {
+            verify::<Ipa, TwoColumn>(dir, &preimage, &digest, &mut record)
+        }
+    };
+    assert!(valid, "{} proof verification failed", meta.mode);
+    println!(
+        "Verified the {} proof of {} recursive hashes of 0^32",
+        meta.mode, meta.k
+    );
+
+    args.output.emit(&record);
+}
+
+fn verify<B: Halo2Backend, H: ChainHasher<B::Scalar>>(
+    dir: &Path,
+    preimage: &[u8; 32],
+    digest: &[u8; 32],
+    record: &mut BenchRecord,
+) -> bool {
+    let instance = chain_instance::<B::Scalar>(preimage, digest);
+    let (params, vk, proof) = record.time("load", || {
+        proof::read::<B, Sha256Chain<H>>(dir)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", dir.display()))
+    });
+    record.time("verify", || B::verify(&params, &vk, &proof, &instance))
+}
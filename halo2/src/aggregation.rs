@@ -0,0 +1,467 @@
+//! Aggregation of SHA256 chain proofs with snark-verifier, the Halo2 counterpart of
+//! folding the chain with Nova.
+//!
+//! The chain is split into chunks of `d` hashes, each proven on its own with KZG (GWC)
+//! and a Poseidon transcript. [`AggregationCircuit`] runs the verifier of every chunk
+//! proof in-circuit up to the final pairing, accumulates the pairings into a single
+//! KZG accumulator and checks that every chunk starts where the previous one ended.
+//! Its public inputs are the limbs of the accumulator, followed by the preimage of the
+//! first chunk and the digest of the last one, like [`crate::circuit::chain_instance`].
+//! The pairing check on the accumulator is left to the verifier, see
+//! [`verify_aggregation`].
+//!
+//! Follows the `evm-verifier-with-accumulator` example of snark-verifier, which has to
+//! be built against the same `halo2_proofs` as this crate, see `Cargo.toml`.
+
+use std::{io::Cursor, rc::Rc};
+
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    halo2curves::{
+        bn256::{Bn256, Fq, Fr, G1Affine},
+        CurveAffine,
+    },
+    plonk::{
+        self, create_proof, verify_proof, Circuit, ConstraintSystem, ProvingKey, VerifyingKey,
+    },
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverGWC, VerifierGWC},
+            strategy::AccumulatorStrategy,
+        },
+        VerificationStrategy,
+    },
+    transcript::{
+        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+    },
+};
+use rand::rngs::OsRng;
+use snark_verifier::{
+    loader::{
+        self,
+        halo2::halo2_wrong_ecc::{
+            self,
+            integer::rns::Rns,
+            maingate::{
+                MainGate, MainGateConfig, MainGateInstructions, RangeChip, RangeConfig,
+                RangeInstructions, RegionCtx,
+            },
+            EccConfig,
+        },
+        native::NativeLoader,
+        ScalarLoader,
+    },
+    pcs::{
+        kzg::{
+            Gwc19, Kzg, KzgAccumulator, KzgAs, KzgDecidingKey, KzgSuccinctVerifyingKey,
+            LimbsEncoding,
+        },
+        AccumulationScheme, AccumulationSchemeProver, Decider,
+    },
+    system::{
+        self,
+        halo2::{compile, Config},
+    },
+    util::arithmetic::{fe_from_limbs, fe_to_limbs},
+    verifier::{self, PlonkVerifier},
+    Protocol,
+};
+
+use crate::circuit::{Sha256Chain, TwoColumn};
+
+const LIMBS: usize = 4;
+const BITS: usize = 68;
+
+/// Parameters of the Poseidon transcript of the chunk proofs.
+const T: usize = 5;
+const RATE: usize = 4;
+const R_F: usize = 8;
+const R_P: usize = 60;
+
+/// Public inputs of a [`Sha256Chain`]: its preimage and its digest, 8 words each.
+const CHAIN_INSTANCE: usize = 16;
+const WORDS: usize = CHAIN_INSTANCE / 2;
+/// Public inputs of the [`AggregationCircuit`].
+const AGGREGATION_INSTANCE: usize = 4 * LIMBS + CHAIN_INSTANCE;
+
+type Pcs = Kzg<Bn256, Gwc19>;
+type As = KzgAs<Pcs>;
+type Plonk = verifier::Plonk<Pcs, LimbsEncoding<LIMBS, BITS>>;
+type Svk = KzgSuccinctVerifyingKey<G1Affine>;
+type BaseFieldEccChip = halo2_wrong_ecc::BaseFieldEccChip<G1Affine, LIMBS, BITS>;
+type Halo2Loader<'a> = loader::halo2::Halo2Loader<'a, G1Affine, BaseFieldEccChip>;
+type PoseidonTranscript<L, S> =
+    system::halo2::transcript::halo2::PoseidonTranscript<G1Affine, L, S, T, RATE, R_F, R_P>;
+
+/// A chunk proof along with what the aggregation circuit needs to verify it.
+pub struct Snark {
+    protocol: Protocol<G1Affine>,
+    instances: Vec<Vec<Fr>>,
+    proof: Vec<u8>,
+}
+
+impl Snark {
+    pub fn proof(&self) -> &[u8] {
+        &self.proof
+    }
+}
+
+#[derive(Clone)]
+struct SnarkWitness {
+    protocol: Protocol<G1Affine>,
+    instances: Vec<Vec<Value<Fr>>>,
+    proof: Value<Vec<u8>>,
+}
+
+impl From<Snark> for SnarkWitness {
+    fn from(snark: Snark) -> Self {
+        Self {
+            protocol: snark.protocol,
+            instances: snark
+                .instances
+                .into_iter()
+                .map(|instances| instances.into_iter().map(Value::known).collect())
+                .collect(),
+            proof: Value::known(snark.proof),
+        }
+    }
+}
+
+impl SnarkWitness {
+    fn without_witnesses(&self) -> Self {
+        Self {
+            protocol: self.protocol.clone(),
+            instances: self
+                .instances
+                .iter()
+                .map(|instances| vec![Value::unknown(); instances.len()])
+                .collect(),
+            proof: Value::unknown(),
+        }
+    }
+
+    fn proof(&self) -> Value<&[u8]> {
+        self.proof.as_ref().map(Vec::as_slice)
+    }
+}
+
+/// Proves a chunk with the Poseidon transcript the aggregation circuit reads.
+pub fn prove_chunk(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: Sha256Chain<TwoColumn>,
+    instance: Vec<Fr>,
+) -> Snark {
+    let mut transcript = PoseidonTranscript::<NativeLoader, Vec<u8>>::init(Vec::new());
+    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit],
+        &[&[&instance]],
+        OsRng,
+        &mut transcript,
+    )
+    .expect("proof generation should not fail");
+    let proof = transcript.finalize();
+
+    let protocol = compile(
+        params,
+        pk.get_vk(),
+        Config::kzg().with_num_instance(vec![CHAIN_INSTANCE]),
+    );
+    Snark {
+        protocol,
+        instances: vec![instance],
+        proof,
+    }
+}
+
+/// Verifies a chunk proof on its own, outside of the aggregation circuit.
+pub fn verify_chunk(params: &ParamsKZG<Bn256>, vk: &VerifyingKey<G1Affine>, snark: &Snark) -> bool {
+    let verifier_params = params.verifier_params();
+    let instances = snark
+        .instances
+        .iter()
+        .map(Vec::as_slice)
+        .collect::<Vec<_>>();
+    let mut transcript =
+        PoseidonTranscript::<NativeLoader, _>::init(Cursor::new(snark.proof.clone()));
+    verify_proof::<KZGCommitmentScheme<_>, VerifierGWC<_>, _, _, _>(
+        verifier_params,
+        vk,
+        AccumulatorStrategy::new(verifier_params),
+        &[instances.as_slice()],
+        &mut transcript,
+    )
+    .map(|strategy| strategy.finalize())
+    .unwrap_or(false)
+}
+
+/// Verifies `snarks` in-circuit and returns the accumulator along with the loaded
+/// public inputs of every snark.
+#[allow(clippy::type_complexity)]
+fn aggregate<'a>(
+    svk: &Svk,
+    loader: &Rc<Halo2Loader<'a>>,
+    snarks: &[SnarkWitness],
+    as_proof: Value<&'_ [u8]>,
+) -> (
+    KzgAccumulator<G1Affine, Rc<Halo2Loader<'a>>>,
+    Vec<Vec<loader::halo2::Scalar<'a, G1Affine, BaseFieldEccChip>>>,
+) {
+    let mut accumulators = Vec::new();
+    let mut chain_instances = Vec::new();
+    for snark in snarks {
+        let protocol = snark.protocol.loaded(loader);
+        let instances = snark
+            .instances
+            .iter()
+            .map(|instances| {
+                instances
+                    .iter()
+                    .map(|instance| loader.assign_scalar(*instance))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut transcript = PoseidonTranscript::<Rc<Halo2Loader>, _>::new(loader, snark.proof());
+        let proof = Plonk::read_proof(svk, &protocol, &instances, &mut transcript);
+        accumulators.extend(Plonk::succinct_verify(svk, &protocol, &instances, &proof));
+        chain_instances.push(instances[0].clone());
+    }
+
+    let mut transcript = PoseidonTranscript::<Rc<Halo2Loader>, _>::new(loader, as_proof);
+    let proof = As::read_proof(&Default::default(), &accumulators, &mut transcript).unwrap();
+    let accumulator = As::verify(&Default::default(), &accumulators, &proof).unwrap();
+    (accumulator, chain_instances)
+}
+
+#[derive(Clone)]
+pub struct AggregationConfig {
+    main_gate_config: MainGateConfig,
+    range_config: RangeConfig,
+}
+
+impl AggregationConfig {
+    fn main_gate(&self) -> MainGate<Fr> {
+        MainGate::new(self.main_gate_config.clone())
+    }
+
+    fn range_chip(&self) -> RangeChip<Fr> {
+        RangeChip::new(self.range_config.clone())
+    }
+
+    fn ecc_chip(&self) -> BaseFieldEccChip {
+        BaseFieldEccChip::new(EccConfig::new(
+            self.range_config.clone(),
+            self.main_gate_config.clone(),
+        ))
+    }
+}
+
+/// Verifies the proofs of consecutive chunks of a chain, see the module docs.
+#[derive(Clone)]
+pub struct AggregationCircuit {
+    svk: Svk,
+    snarks: Vec<SnarkWitness>,
+    instances: Vec<Fr>,
+    as_proof: Value<Vec<u8>>,
+}
+
+impl AggregationCircuit {
+    /// Accumulates `snarks` natively, which gives the witness of the circuit.
+    pub fn new(params: &ParamsKZG<Bn256>, snarks: Vec<Snark>) -> Self {
+        assert!(!snarks.is_empty(), "at least one chunk is needed");
+        let svk: Svk = params.get_g()[0].into();
+
+        let accumulators = snarks
+            .iter()
+            .flat_map(|snark| {
+                let mut transcript =
+                    PoseidonTranscript::<NativeLoader, _>::new(snark.proof.as_slice());
+                let proof =
+                    Plonk::read_proof(&svk, &snark.protocol, &snark.instances, &mut transcript);
+                Plonk::succinct_verify(&svk, &snark.protocol, &snark.instances, &proof)
+            })
+            .collect::<Vec<_>>();
+
+        let mut transcript = PoseidonTranscript::<NativeLoader, _>::new(Vec::new());
+        let accumulator =
+            As::create_proof(&Default::default(), &accumulators, &mut transcript, OsRng).unwrap();
+        let as_proof = transcript.finalize();
+
+        let KzgAccumulator { lhs, rhs } = accumulator;
+        let mut instances = [lhs.x, lhs.y, rhs.x, rhs.y]
+            .map(fe_to_limbs::<_, _, LIMBS, BITS>)
+            .concat();
+        instances.extend_from_slice(&snarks[0].instances[0][..WORDS]);
+        instances.extend_from_slice(&snarks[snarks.len() - 1].instances[0][WORDS..]);
+
+        Self {
+            svk,
+            snarks: snarks.into_iter().map(SnarkWitness::from).collect(),
+            instances,
+            as_proof: Value::known(as_proof),
+        }
+    }
+
+    pub fn instances(&self) -> Vec<Fr> {
+        self.instances.clone()
+    }
+
+    fn as_proof(&self) -> Value<&[u8]> {
+        self.as_proof.as_ref().map(Vec::as_slice)
+    }
+}
+
+impl Circuit<Fr> for AggregationCircuit {
+    type Config = AggregationConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            svk: self.svk,
+            snarks: self
+                .snarks
+                .iter()
+                .map(SnarkWitness::without_witnesses)
+                .collect(),
+            instances: Vec::new(),
+            as_proof: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let main_gate_config = MainGate::<Fr>::configure(meta);
+        let range_config = RangeChip::<Fr>::configure(
+            meta,
+            &main_gate_config,
+            vec![BITS / LIMBS],
+            Rns::<Fq, Fr, LIMBS, BITS>::construct().overflow_lengths(),
+        );
+        AggregationConfig {
+            main_gate_config,
+            range_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), plonk::Error> {
+        let main_gate = config.main_gate();
+        let range_chip = config.range_chip();
+        range_chip.load_table(&mut layouter)?;
+
+        let public = layouter.assign_region(
+            || "aggregation",
+            |region| {
+                let ctx = RegionCtx::new(region, 0);
+                let loader = Halo2Loader::new(config.ecc_chip(), ctx);
+                let (accumulator, chain_instances) =
+                    aggregate(&self.svk, &loader, &self.snarks, self.as_proof());
+
+                // Every chunk has to start from the digest of the previous one.
+                for (prev, next) in chain_instances.iter().zip(&chain_instances[1..]) {
+                    for (digest, preimage) in prev[WORDS..].iter().zip(&next[..WORDS]) {
+                        loader
+                            .assert_eq("chunks are chained", digest, preimage)
+                            .expect("failed to chain the chunks");
+                    }
+                }
+
+                let mut public = [accumulator.lhs, accumulator.rhs]
+                    .iter()
+                    .map(|ec_point| {
+                        loader
+                            .ecc_chip()
+                            .assign_ec_point_to_limbs(&mut loader.ctx_mut(), ec_point.assigned())
+                    })
+                    .collect::<Result<Vec<_>, plonk::Error>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                let first = &chain_instances[0];
+                let last = &chain_instances[chain_instances.len() - 1];
+                public.extend(
+                    first[..WORDS]
+                        .iter()
+                        .chain(&last[WORDS..])
+                        .map(|scalar| scalar.assigned()),
+                );
+                Ok(public)
+            },
+        )?;
+
+        for (row, cell) in public.into_iter().enumerate() {
+            main_gate.expose_public(layouter.namespace(|| "public"), cell, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Proves the aggregation circuit with the Blake2b transcript, like the other backends.
+pub fn prove_aggregation(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: AggregationCircuit,
+) -> Vec<u8> {
+    let instances = circuit.instances();
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit],
+        &[&[&instances]],
+        OsRng,
+        &mut transcript,
+    )
+    .expect("proof generation should not fail");
+    transcript.finalize()
+}
+
+/// Verifies the aggregation proof and then the accumulator in its public inputs,
+/// which stands for the pairings of all chunk proofs.
+pub fn verify_aggregation(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    proof: &[u8],
+    instances: &[Fr],
+) -> bool {
+    assert_eq!(instances.len(), AGGREGATION_INSTANCE);
+    let verifier_params = params.verifier_params();
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+    let valid = verify_proof::<KZGCommitmentScheme<_>, VerifierGWC<_>, _, _, _>(
+        verifier_params,
+        vk,
+        AccumulatorStrategy::new(verifier_params),
+        &[&[instances]],
+        &mut transcript,
+    )
+    .map(|strategy| strategy.finalize())
+    .unwrap_or(false);
+    if !valid {
+        return false;
+    }
+
+    let [lhs_x, lhs_y, rhs_x, rhs_y]: [Fq; 4] = std::array::from_fn(|i| {
+        let limbs = instances[i * LIMBS..(i + 1) * LIMBS].try_into().unwrap();
+        fe_from_limbs::<_, _, LIMBS, BITS>(limbs)
+    });
+    let (Some(lhs), Some(rhs)) = (
+        Option::from(G1Affine::from_xy(lhs_x, lhs_y)),
+        Option::from(G1Affine::from_xy(rhs_x, rhs_y)),
+    ) else {
+        return false;
+    };
+    let dk: KzgDecidingKey<Bn256> = (params.get_g()[0], params.g2(), params.s_g2()).into();
+    Pcs::decide(&dk, KzgAccumulator::new(lhs, rhs))
+}
+
+/// Public inputs of the aggregation circuit besides the accumulator, i.e. those of the
+/// whole chain.
+pub fn chain_public_inputs(instances: &[Fr]) -> &[Fr] {
+    &instances[4 * LIMBS..]
+}
@@ -0,0 +1,388 @@
+//! Commitment schemes the SHA256 chain can be proven with.
+//!
+//! KZG runs on BN254, IPA on the Pasta curves, i.e. the same cycle Nova uses. Each
+//! scheme implements [`Halo2Backend`] and is picked at runtime with [`Backend`].
+
+use std::{
+    io::{self, Read, Write},
+    marker::PhantomData,
+};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        pasta::{EqAffine, Fp},
+        CurveAffine,
+    },
+    plonk::{create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, ProvingKey, VerifyingKey},
+    poly::{
+        commitment::{Params, ParamsProver},
+        ipa::{
+            commitment::{IPACommitmentScheme, ParamsIPA},
+            multiopen::{ProverIPA, VerifierIPA},
+            strategy::AccumulatorStrategy as IpaAccumulatorStrategy,
+        },
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverGWC, ProverSHPLONK, VerifierGWC, VerifierSHPLONK},
+            strategy::AccumulatorStrategy as KzgAccumulatorStrategy,
+        },
+        VerificationStrategy,
+    },
+    transcript::{
+        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+    },
+    SerdeFormat,
+};
+use rand::rngs::OsRng;
+
+use crate::params::{circuit_size, CircuitSize};
+
+/// Which [`Halo2Backend`] to benchmark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Backend {
+    /// KZG on BN254 with the GWC multiopen.
+    KzgGwc,
+    /// KZG on BN254 with the SHPLONK multiopen, smaller proofs than GWC.
+    KzgShplonk,
+    /// IPA on Pasta (Vesta commitments, Pallas base field circuit).
+    Ipa,
+}
+
+impl Backend {
+    /// Mode of the result records, the same as [`Halo2Backend::NAME`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::KzgGwc => KzgGwc::NAME,
+            Backend::KzgShplonk => KzgShplonk::NAME,
+            Backend::Ipa => Ipa::NAME,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Backend::KzgGwc, Backend::KzgShplonk, Backend::Ipa]
+            .into_iter()
+            .find(|backend| backend.name() == name)
+    }
+}
+
+/// A commitment scheme and multiopen argument, along with the curve they run on.
+pub trait Halo2Backend {
+    /// Mode of the result records, e.g. `kzg-gwc`.
+    const NAME: &'static str;
+    const CURVE: &'static str;
+
+    type Scalar: FieldExt;
+    type Curve: CurveAffine<ScalarExt = Self::Scalar>;
+    type Params;
+
+    /// Generates a new SRS of size `2^k`.
+    fn setup(k: u32) -> Self::Params;
+    fn read_params<R: Read>(reader: &mut R) -> io::Result<Self::Params>;
+    fn write_params<W: Write>(params: &Self::Params, writer: &mut W) -> io::Result<()>;
+    /// log2 of the size of `params`.
+    fn params_k(params: &Self::Params) -> u32;
+    fn downsize(params: &mut Self::Params, k: u32);
+
+    fn circuit_size<C: Circuit<Self::Scalar>>(circuit: &C) -> CircuitSize;
+    fn keygen<C: Circuit<Self::Scalar>>(
+        params: &Self::Params,
+        circuit: &C,
+    ) -> ProvingKey<Self::Curve>;
+    fn read_vk<R: Read, C: Circuit<Self::Scalar>>(
+        reader: &mut R,
+    ) -> io::Result<VerifyingKey<Self::Curve>>;
+    fn write_vk<W: Write>(vk: &VerifyingKey<Self::Curve>, writer: &mut W) -> io::Result<()>;
+
+    /// Proves `circuit` with the Blake2b transcript and returns the transcript.
+    fn prove<C: Circuit<Self::Scalar>>(
+        params: &Self::Params,
+        pk: &ProvingKey<Self::Curve>,
+        circuit: C,
+        instance: &[Self::Scalar],
+    ) -> Vec<u8>;
+    fn verify(
+        params: &Self::Params,
+        vk: &VerifyingKey<Self::Curve>,
+        proof: &[u8],
+        instance: &[Self::Scalar],
+    ) -> bool;
+}
+
+/// Multiopen argument of [`Kzg`], the only part in which the KZG backends differ.
+pub trait KzgMultiopen {
+    const NAME: &'static str;
+
+    fn prove<C: Circuit<Fr>>(
+        params: &ParamsKZG<Bn256>,
+        pk: &ProvingKey<G1Affine>,
+        circuit: C,
+        instance: &[Fr],
+    ) -> Vec<u8>;
+    fn verify(
+        params: &ParamsKZG<Bn256>,
+        vk: &VerifyingKey<G1Affine>,
+        proof: &[u8],
+        instance: &[Fr],
+    ) -> bool;
+}
+
+pub struct Gwc;
+
+impl KzgMultiopen for Gwc {
+    const NAME: &'static str = "kzg-gwc";
+
+    fn prove<C: Circuit<Fr>>(
+        params: &ParamsKZG<Bn256>,
+        pk: &ProvingKey<G1Affine>,
+        circuit: C,
+        instance: &[Fr],
+    ) -> Vec<u8> {
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+            params,
+            pk,
+            &[circuit],
+            &[&[instance]],
+            OsRng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        transcript.finalize()
+    }
+
+    fn verify(
+        params: &ParamsKZG<Bn256>,
+        vk: &VerifyingKey<G1Affine>,
+        proof: &[u8],
+        instance: &[Fr],
+    ) -> bool {
+        let verifier_params = params.verifier_params();
+        let strategy = KzgAccumulatorStrategy::new(verifier_params);
+        let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+        verify_proof::<KZGCommitmentScheme<_>, VerifierGWC<_>, _, _, _>(
+            verifier_params,
+            vk,
+            strategy,
+            &[&[instance]],
+            &mut transcript,
+        )
+        .map(|strategy| strategy.finalize())
+        .unwrap_or(false)
+    }
+}
+
+pub struct Shplonk;
+
+impl KzgMultiopen for Shplonk {
+    const NAME: &'static str = "kzg-shplonk";
+
+    fn prove<C: Circuit<Fr>>(
+        params: &ParamsKZG<Bn256>,
+        pk: &ProvingKey<G1Affine>,
+        circuit: C,
+        instance: &[Fr],
+    ) -> Vec<u8> {
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof::<KZGCommitmentScheme<_>, ProverSHPLONK<_>, _, _, _, _>(
+            params,
+            pk,
+            &[circuit],
+            &[&[instance]],
+            OsRng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        transcript.finalize()
+    }
+
+    fn verify(
+        params: &ParamsKZG<Bn256>,
+        vk: &VerifyingKey<G1Affine>,
+        proof: &[u8],
+        instance: &[Fr],
+    ) -> bool {
+        let verifier_params = params.verifier_params();
+        let strategy = KzgAccumulatorStrategy::new(verifier_params);
+        let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+        verify_proof::<KZGCommitmentScheme<_>, VerifierSHPLONK<_>, _, _, _>(
+            verifier_params,
+            vk,
+            strategy,
+            &[&[instance]],
+            &mut transcript,
+        )
+        .map(|strategy| strategy.finalize())
+        .unwrap_or(false)
+    }
+}
+
+/// KZG on BN254 with multiopen argument `M`.
+pub struct Kzg<M>(PhantomData<M>);
+
+pub type KzgGwc = Kzg<Gwc>;
+pub type KzgShplonk = Kzg<Shplonk>;
+
+impl<M: KzgMultiopen> Halo2Backend for Kzg<M> {
+    const NAME: &'static str = M::NAME;
+    const CURVE: &'static str = "bn254";
+
+    type Scalar = Fr;
+    type Curve = G1Affine;
+    type Params = ParamsKZG<Bn256>;
+
+    fn setup(k: u32) -> Self::Params {
+        ParamsKZG::setup(k, OsRng)
+    }
+
+    fn read_params<R: Read>(reader: &mut R) -> io::Result<Self::Params> {
+        ParamsKZG::read(reader)
+    }
+
+    fn write_params<W: Write>(params: &Self::Params, writer: &mut W) -> io::Result<()> {
+        params.write(writer)
+    }
+
+    fn params_k(params: &Self::Params) -> u32 {
+        params.k()
+    }
+
+    fn downsize(params: &mut Self::Params, k: u32) {
+        params.downsize(k)
+    }
+
+    fn circuit_size<C: Circuit<Self::Scalar>>(circuit: &C) -> CircuitSize {
+        circuit_size::<Fr, _>(circuit)
+    }
+
+    fn keygen<C: Circuit<Self::Scalar>>(
+        params: &Self::Params,
+        circuit: &C,
+    ) -> ProvingKey<G1Affine> {
+        let vk = keygen_vk(params, circuit).expect("keygen_vk should not fail");
+        keygen_pk(params, vk, circuit).expect("keygen_pk should not fail")
+    }
+
+    fn read_vk<R: Read, C: Circuit<Self::Scalar>>(
+        reader: &mut R,
+    ) -> io::Result<VerifyingKey<G1Affine>> {
+        VerifyingKey::read::<_, C>(reader, SerdeFormat::RawBytes)
+    }
+
+    fn write_vk<W: Write>(vk: &VerifyingKey<G1Affine>, writer: &mut W) -> io::Result<()> {
+        vk.write(writer, SerdeFormat::RawBytes)
+    }
+
+    fn prove<C: Circuit<Self::Scalar>>(
+        params: &Self::Params,
+        pk: &ProvingKey<G1Affine>,
+        circuit: C,
+        instance: &[Fr],
+    ) -> Vec<u8> {
+        M::prove(params, pk, circuit, instance)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        vk: &VerifyingKey<G1Affine>,
+        proof: &[u8],
+        instance: &[Fr],
+    ) -> bool {
+        M::verify(params, vk, proof, instance)
+    }
+}
+
+/// IPA over Pasta. The params are transparent, [`Halo2Backend::setup`] is
+/// deterministic.
+pub struct Ipa;
+
+impl Halo2Backend for Ipa {
+    const NAME: &'static str = "ipa";
+    const CURVE: &'static str = "pasta";
+
+    type Scalar = Fp;
+    type Curve = EqAffine;
+    type Params = ParamsIPA<EqAffine>;
+
+    fn setup(k: u32) -> Self::Params {
+        ParamsIPA::new(k)
+    }
+
+    fn read_params<R: Read>(reader: &mut R) -> io::Result<Self::Params> {
+        ParamsIPA::read(reader)
+    }
+
+    fn write_params<W: Write>(params: &Self::Params, writer: &mut W) -> io::Result<()> {
+        params.write(writer)
+    }
+
+    fn params_k(params: &Self::Params) -> u32 {
+        params.k()
+    }
+
+    fn downsize(params: &mut Self::Params, k: u32) {
+        params.downsize(k)
+    }
+
+    fn circuit_size<C: Circuit<Self::Scalar>>(circuit: &C) -> CircuitSize {
+        circuit_size::<Fp, _>(circuit)
+    }
+
+    fn keygen<C: Circuit<Self::Scalar>>(
+        params: &Self::Params,
+        circuit: &C,
+    ) -> ProvingKey<EqAffine> {
+        let vk = keygen_vk(params, circuit).expect("keygen_vk should not fail");
+        keygen_pk(params, vk, circuit).expect("keygen_pk should not fail")
+    }
+
+    fn read_vk<R: Read, C: Circuit<Self::Scalar>>(
+        reader: &mut R,
+    ) -> io::Result<VerifyingKey<EqAffine>> {
+        VerifyingKey::read::<_, C>(reader, SerdeFormat::RawBytes)
+    }
+
+    fn write_vk<W: Write>(vk: &VerifyingKey<EqAffine>, writer: &mut W) -> io::Result<()> {
+        vk.write(writer, SerdeFormat::RawBytes)
+    }
+
+    fn prove<C: Circuit<Self::Scalar>>(
+        params: &Self::Params,
+        pk: &ProvingKey<EqAffine>,
+        circuit: C,
+        instance: &[Fp],
+    ) -> Vec<u8> {
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof::<IPACommitmentScheme<_>, ProverIPA<_>, _, _, _, _>(
+            params,
+            pk,
+            &[circuit],
+            &[&[instance]],
+            OsRng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        transcript.finalize()
+    }
+
+    fn verify(
+        params: &Self::Params,
+        vk: &VerifyingKey<EqAffine>,
+        proof: &[u8],
+        instance: &[Fp],
+    ) -> bool {
+        let verifier_params = params.verifier_params();
+        let strategy = IpaAccumulatorStrategy::new(verifier_params);
+        let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+        verify_proof::<IPACommitmentScheme<_>, VerifierIPA<_>, _, _, _>(
+            verifier_params,
+            vk,
+            strategy,
+            &[&[instance]],
+            &mut transcript,
+        )
+        .map(|strategy| strategy.finalize())
+        .unwrap_or(false)
+    }
+}
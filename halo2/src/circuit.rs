@@ -0,0 +1,292 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use halo2_gadgets::sha256::{BlockWord, Sha256, Table16Chip, Table16Config, DIGEST_SIZE};
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+    halo2curves::bn256::Fr,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+pub use crate::two_column::TwoColumn;
+
+/// SHA256 padding of a 32-byte message: the `1` bit, zeros and the length in bits.
+/// The chips don't pad, so it is appended to every preimage to get the same digests
+/// as the Circom and Nova circuits, which hash 32 bytes at a time too.
+pub(crate) const PADDING: [u32; DIGEST_SIZE] = [0x8000_0000, 0, 0, 0, 0, 0, 0, 256];
+
+/// Which SHA256 chip to build the chain with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Chip {
+    /// [`Table16Chip`] of `halo2_gadgets`, lookup based.
+    Table16,
+    /// [`TwoColumnChip`](crate::two_column::TwoColumnChip), two advice columns and no
+    /// lookups.
+    TwoColumn,
+}
+
+impl Chip {
+    /// Value of the `chip` param of the result records, the same as
+    /// [`ChainHasher::NAME`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Chip::Table16 => <Table16 as ChainHasher<Fr>>::NAME,
+            Chip::TwoColumn => <TwoColumn as ChainHasher<Fr>>::NAME,
+        }
+    }
+
+    /// [`ChainHasher::SOUND`] of the chip.
+    pub fn sound(&self) -> bool {
+        match self {
+            Chip::Table16 => <Table16 as ChainHasher<Fr>>::SOUND,
+            Chip::TwoColumn => <TwoColumn as ChainHasher<Fr>>::SOUND,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Chip::Table16, Chip::TwoColumn]
+            .into_iter()
+            .find(|chip| chip.name() == name)
+    }
+}
+
+/// A SHA256 chip [`Sha256Chain`] can be built with.
+pub trait ChainHasher<F: FieldExt> {
+    const NAME: &'static str;
+
+    /// Whether the public preimage and digest are the ends of the hashed chain, i.e.
+    /// whether a proof shows that `digest = h^k(preimage)` like the Nova and Circom
+    /// ones do. The records of a chip that doesn't are marked with `sound: false`.
+    const SOUND: bool;
+
+    type Config: Clone + Debug;
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config;
+
+    /// Hashes `preimage`, padded with [`PADDING`], `iter_num` times. Returns the cells
+    /// of the words of the preimage followed by those of the digest, which are
+    /// constrained to the instance column.
+    fn hash_chain(
+        config: Self::Config,
+        layouter: impl Layouter<F>,
+        preimage: Value<[u32; DIGEST_SIZE]>,
+        iter_num: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error>;
+}
+
+/// `iter_num` recursive hashes of `preimage` with the SHA256 chip `H`. The preimage
+/// and the final digest are exposed as 8 big-endian 32-bit words each in the instance
+/// column, see [`chain_instance`], so that chains can be linked up. The chip defaults
+/// to [`TwoColumn`], the one whose proofs show that the digest is that of the preimage.
+#[derive(Clone, Debug, Default)]
+pub struct Sha256Chain<H = TwoColumn> {
+    pub preimage: Value<[u32; DIGEST_SIZE]>,
+    pub iter_num: usize,
+    _hasher: PhantomData<H>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sha256ChainConfig<C> {
+    hasher: C,
+    instance: Column<Instance>,
+}
+
+impl<H> Sha256Chain<H> {
+    pub fn new(preimage: [u8; 32], iter_num: usize) -> Self {
+        Self {
+            preimage: Value::known(to_words(&preimage)),
+            iter_num,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt, H: ChainHasher<F>> Circuit<F> for Sha256Chain<H> {
+    type Config = Sha256ChainConfig<H::Config>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            preimage: Value::unknown(),
+            iter_num: self.iter_num,
+            _hasher: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let hasher = H::configure(meta);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        Sha256ChainConfig { hasher, instance }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let public = H::hash_chain(
+            config.hasher,
+            layouter.namespace(|| "chain"),
+            self.preimage,
+            self.iter_num,
+        )?;
+        for (i, cell) in public.iter().enumerate() {
+            layouter.constrain_instance(cell.cell(), config.instance, i)?;
+        }
+        Ok(())
+    }
+}
+
+/// [`ChainHasher`] of the lookup based [`Table16Chip`].
+///
+/// Table16 only takes and hands back the values of the words, not their cells, so
+/// the preimage and the digest are witnessed again in a column of their own before
+/// being constrained to the instance column, and so is every digest fed into the next
+/// hash. Nothing ties those to the hashes, so a proof only shows that some blocks
+/// were hashed, not that the public digest is that of the public preimage: it isn't
+/// [`SOUND`](ChainHasher::SOUND) until `halo2_gadgets` hands out the cells of the
+/// message and the digest. See [`TwoColumn`] for a chip that is constrained end to
+/// end.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Table16;
+
+#[derive(Clone, Debug)]
+pub struct Table16HasherConfig {
+    table16: Table16Config,
+    public: Column<Advice>,
+}
+
+impl<F: FieldExt> ChainHasher<F> for Table16 {
+    const NAME: &'static str = "table16";
+    const SOUND: bool = false;
+
+    type Config = Table16HasherConfig;
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let table16 = Table16Chip::configure(meta);
+        let public = meta.advice_column();
+        meta.enable_equality(public);
+
+        Table16HasherConfig { table16, public }
+    }
+
+    fn hash_chain(
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+        preimage: Value<[u32; DIGEST_SIZE]>,
+        iter_num: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        Table16Chip::load(config.table16.clone(), &mut layouter)?;
+        let table16_chip = Table16Chip::construct(config.table16);
+
+        let mut digest: [BlockWord; DIGEST_SIZE] =
+            std::array::from_fn(|i| BlockWord(preimage.map(|preimage| preimage[i])));
+
+        for _ in 0..iter_num {
+            let block = digest
+                .into_iter()
+                .chain(PADDING.map(|word| BlockWord(Value::known(word))))
+                .collect::<Vec<_>>();
+            digest = Sha256::digest(
+                table16_chip.clone(),
+                layouter.namespace(|| "sha256"),
+                &block,
+            )?
+            .0;
+        }
+
+        let preimage = (0..DIGEST_SIZE).map(|i| preimage.map(|preimage| preimage[i]));
+        let public = preimage
+            .chain(digest.iter().map(|word| word.0))
+            .collect::<Vec<_>>();
+        layouter.assign_region(
+            || "public",
+            |mut region| {
+                public
+                    .iter()
+                    .enumerate()
+                    .map(|(i, word)| {
+                        region.assign_advice(
+                            || format!("public[{i}]"),
+                            config.public,
+                            i,
+                            || word.map(|word| F::from(word as u64)),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )
+    }
+}
+
+fn to_words(bytes: &[u8; 32]) -> [u32; DIGEST_SIZE] {
+    let mut words = [0; DIGEST_SIZE];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+/// Public inputs of a [`Sha256Chain`] from `preimage` to `digest`.
+pub fn chain_instance<F: FieldExt>(preimage: &[u8; 32], digest: &[u8; 32]) -> Vec<F> {
+    to_words(preimage)
+        .iter()
+        .chain(&to_words(digest))
+        .map(|&word| F::from(word as u64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
+    use nova_bench::sha256::sha256_chain_digest;
+
+    use super::*;
+    use crate::params::circuit_size;
+
+    #[test]
+    fn default_chip_rejects_a_wrong_digest() {
+        let circuit: Sha256Chain = Sha256Chain::new([0; 32], 1);
+        let k = circuit_size::<Fr, _>(&circuit).min_k;
+        let digest = sha256_chain_digest([0; 32], 1);
+
+        let instance = chain_instance::<Fr>(&[0; 32], &digest);
+        let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        for byte in [0, 31] {
+            let mut wrong = digest;
+            wrong[byte] ^= 1;
+            let instance = chain_instance::<Fr>(&[0; 32], &wrong);
+            let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+
+        let mut preimage = [0; 32];
+        preimage[0] = 1;
+        let instance = chain_instance::<Fr>(&preimage, &digest);
+        let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn digest_matches_native_sha256() {
+        let circuit = Sha256Chain::<Table16>::new([0; 32], 2);
+        let digest = sha256_chain_digest([0; 32], 2);
+
+        let instance = chain_instance::<Fr>(&[0; 32], &digest);
+        let prover = MockProver::run(17, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let wrong = sha256_chain_digest([0; 32], 1);
+        let instance = chain_instance::<Fr>(&[0; 32], &wrong);
+        let prover = MockProver::run(17, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let instance = chain_instance::<Fr>(&[1; 32], &digest);
+        let prover = MockProver::run(17, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
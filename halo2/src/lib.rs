@@ -0,0 +1,9 @@
+//! Recursive SHA256 hashing with Halo2, shared by the `sha256` and `verify` examples.
+
+#[cfg(feature = "aggregation")]
+pub mod aggregation;
+pub mod backend;
+pub mod circuit;
+pub mod params;
+pub mod proof;
+pub mod two_column;
@@ -0,0 +1,249 @@
+//! The SRS: how large it needs to be for a circuit, and reading and writing it. Also
+//! the shape of a circuit, see [`circuit_stats`].
+//!
+//! SRS files use the format of `Params::write` of the backend. For KZG, besides files
+//! written by the benchmark itself, this is the format the powers-of-tau of the Hermez
+//! and perpetual powers-of-tau ceremonies are converted to by e.g.
+//! `han0110/halo2-kzg-srs`, so those can be used instead of an SRS generated from
+//! local randomness.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::Value,
+    plonk::{
+        Advice, Any, Assigned, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed,
+        FloorPlanner, Instance, Selector,
+    },
+};
+
+use crate::backend::Halo2Backend;
+
+/// Rows used by a circuit and the smallest SRS (log2 of its size) that fits them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitSize {
+    /// Rows used by the layout, e.g. by the regions and lookup tables.
+    pub rows: usize,
+    /// Rows the prover reserves for blinding.
+    pub reserved_rows: usize,
+    pub min_k: u32,
+}
+
+/// Shape of a circuit: its columns and constraints, and the rows it uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitStats {
+    pub advice_columns: usize,
+    pub fixed_columns: usize,
+    pub instance_columns: usize,
+    /// Selectors, before they are turned into fixed columns.
+    pub selectors: usize,
+    /// Columns in the permutation argument, i.e. with equality enabled.
+    pub permutation_columns: usize,
+    pub gates: usize,
+    /// Polynomial constraints of the gates.
+    pub constraints: usize,
+    pub lookups: usize,
+    /// Maximum degree of the constraints and arguments.
+    pub degree: usize,
+    pub size: CircuitSize,
+}
+
+/// Lays out `circuit` without its witness and counts the rows it uses.
+pub fn circuit_size<F: Field, C: Circuit<F>>(circuit: &C) -> CircuitSize {
+    circuit_stats(circuit).size
+}
+
+/// Lays out `circuit` without its witness and collects its [`CircuitStats`].
+pub fn circuit_stats<F: Field, C: Circuit<F>>(circuit: &C) -> CircuitStats {
+    let mut cs = ConstraintSystem::default();
+    let config = C::configure(&mut cs);
+    let mut counter = RowCounter::default();
+    C::FloorPlanner::synthesize(
+        &mut counter,
+        &circuit.without_witnesses(),
+        config,
+        cs.constants().clone(),
+    )
+    .expect("failed to lay out the circuit");
+
+    let rows = counter.rows;
+    let reserved_rows = cs.minimum_rows();
+    let min_k = (rows + reserved_rows).next_power_of_two().trailing_zeros();
+    CircuitStats {
+        advice_columns: cs.num_advice_columns(),
+        fixed_columns: cs.num_fixed_columns(),
+        instance_columns: cs.num_instance_columns(),
+        selectors: cs.num_selectors(),
+        permutation_columns: cs.permutation().get_columns().len(),
+        gates: cs.gates().len(),
+        constraints: cs.gates().iter().map(|gate| gate.polynomials().len()).sum(),
+        lookups: cs.lookups().len(),
+        degree: cs.degree(),
+        size: CircuitSize {
+            rows,
+            reserved_rows,
+            min_k,
+        },
+    }
+}
+
+/// Reads the SRS in `path` and downsizes it to `2^k`. Fails if it is smaller than that.
+pub fn read_srs<B: Halo2Backend>(path: &Path, k: u32) -> io::Result<B::Params> {
+    let mut params = B::read_params(&mut BufReader::new(File::open(path)?))?;
+    let srs_k = B::params_k(&params);
+    if srs_k < k {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the SRS is of size 2^{srs_k}, 2^{k} is needed"),
+        ));
+    }
+    if srs_k > k {
+        B::downsize(&mut params, k);
+    }
+    Ok(params)
+}
+
+pub fn write_srs<B: Halo2Backend>(path: &Path, params: &B::Params) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    B::write_params(params, &mut writer)?;
+    writer.flush()
+}
+
+/// [`Assignment`] that only keeps track of the highest row used. It has to follow the
+/// trait in the halo2 fork, the tests check its count against `MockProver`.
+#[derive(Default)]
+struct RowCounter {
+    rows: usize,
+}
+
+impl RowCounter {
+    fn use_row(&mut self, row: usize) {
+        self.rows = self.rows.max(row + 1);
+    }
+}
+
+impl<F: Field> Assignment<F> for RowCounter {
+    fn enter_region<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn exit_region(&mut self) {}
+
+    fn enable_selector<A, AR>(&mut self, _: A, _: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.use_row(row);
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Advice>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.use_row(row);
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.use_row(row);
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        _: Column<Any>,
+        left_row: usize,
+        _: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        self.use_row(left_row.max(right_row));
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
+    use nova_bench::sha256::sha256_chain_digest;
+
+    use super::*;
+    use crate::circuit::{chain_instance, ChainHasher, Sha256Chain, Table16, TwoColumn};
+
+    /// Cross-checks the rows counted by `RowCounter` with `MockProver`: a hash of `H`
+    /// fits in `min_k` and doesn't in `min_k - 1`.
+    fn assert_min_k_matches_mock_prover<H: ChainHasher<Fr>>() {
+        let circuit = Sha256Chain::<H>::new([0; 32], 1);
+        let size = circuit_size::<Fr, _>(&circuit);
+        assert!(size.rows + size.reserved_rows <= 1 << size.min_k);
+        assert!(size.rows + size.reserved_rows > 1 << (size.min_k - 1));
+
+        let instance = chain_instance::<Fr>(&[0; 32], &sha256_chain_digest([0; 32], 1));
+        let prover = MockProver::run(size.min_k, &circuit, vec![instance.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let too_small = MockProver::run(size.min_k - 1, &circuit, vec![instance]);
+        assert!(too_small.map_or(true, |prover| prover.verify().is_err()));
+    }
+
+    #[test]
+    fn min_k_fits_the_circuit() {
+        assert_min_k_matches_mock_prover::<Table16>();
+        assert_min_k_matches_mock_prover::<TwoColumn>();
+
+        let size = circuit_size::<Fr, _>(&Sha256Chain::<Table16>::new([0; 32], 1));
+        assert!(circuit_size::<Fr, _>(&Sha256Chain::<Table16>::new([0; 32], 2)).rows > size.rows);
+    }
+}
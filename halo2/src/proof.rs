@@ -0,0 +1,51 @@
+//! Proofs on disk, see `nova_bench::proof` for the layout of a proof directory.
+//!
+//! `proof.bin` is the Blake2b transcript as is. The verifier needs the params along
+//! with the verifying key, so `vk.bin` holds both, one after the other. Which
+//! [`Halo2Backend`] wrote them is the mode in `meta.json`, and which SHA256 chip
+//! the circuit is built with its `chip` param.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use halo2_proofs::plonk::{Circuit, VerifyingKey};
+use nova_bench::{
+    proof::{proof_path, vk_path, ProofMeta},
+    report::BenchRecord,
+};
+
+use crate::backend::Halo2Backend;
+
+/// Writes `proof` and what is needed to verify it to `dir`, along with the metadata
+/// of `record`.
+pub fn write<B: Halo2Backend>(
+    dir: &Path,
+    params: &B::Params,
+    vk: &VerifyingKey<B::Curve>,
+    proof: &[u8],
+    record: &BenchRecord,
+) -> io::Result<()> {
+    ProofMeta::from_record(record).write(dir)?;
+    fs::write(proof_path(dir), proof)?;
+
+    let mut writer = BufWriter::new(File::create(vk_path(dir))?);
+    B::write_params(params, &mut writer)?;
+    B::write_vk(vk, &mut writer)?;
+    writer.flush()
+}
+
+/// Reads back what [`write`] wrote for circuit `C`: the params, the verifying key and
+/// the proof.
+#[allow(clippy::type_complexity)]
+pub fn read<B: Halo2Backend, C: Circuit<B::Scalar>>(
+    dir: &Path,
+) -> io::Result<(B::Params, VerifyingKey<B::Curve>, Vec<u8>)> {
+    let mut reader = BufReader::new(File::open(vk_path(dir))?);
+    let params = B::read_params(&mut reader)?;
+    let vk = B::read_vk::<_, C>(&mut reader)?;
+    let proof = fs::read(proof_path(dir))?;
+    Ok((params, vk, proof))
+}
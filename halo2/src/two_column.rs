@@ -0,0 +1,696 @@
+//! SHA256 on two advice columns with plain custom gates and no lookups.
+//!
+//! Words are kept as 32 boolean cells, least significant bit first, so rotations and
+//! shifts only pick other cells. The bitwise functions are gates on single bits, two
+//! rows a bit, and additions go through their field value: a running sum down column
+//! `b` adds the bits of the summands and subtracts the bits of the result and of the
+//! carry, and has to end at zero. Besides the two advice columns there is a fixed
+//! column for the coefficients of the sums and one for constants.
+//!
+//! It is the counterpart of [`Table16Chip`](halo2_gadgets::sha256::Table16Chip) that
+//! only differs in the arithmetization, for comparing with the lookup based layout and
+//! with R1CS. Unlike Table16, it hands back the cells of the words, see [`TwoColumn`].
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, Selector},
+    poly::Rotation,
+};
+
+use crate::circuit::{ChainHasher, PADDING};
+
+const WORD_BITS: usize = 32;
+/// Bits of the carry of a sum of up to 7 words, i.e. up to 6 words and a constant.
+const CARRY_BITS: usize = 3;
+
+const IV: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+#[derive(Clone, Debug)]
+pub struct TwoColumnConfig {
+    a: Column<Advice>,
+    b: Column<Advice>,
+    coeff: Column<Fixed>,
+    /// `a` is a bit.
+    q_bool: Selector,
+    /// `b` of the next row is `b + coeff * a` of the next row.
+    q_lin: Selector,
+    /// `b` of the next row is `x ^ y ^ z` of `a`, `b` and `a` of the next row.
+    q_xor: Selector,
+    /// Same as `q_xor` for `Ch(x, y, z)`.
+    q_ch: Selector,
+    /// Same as `q_xor` for `Maj(x, y, z)`.
+    q_maj: Selector,
+}
+
+/// A 32-bit word as its bits, least significant first.
+#[derive(Clone, Debug)]
+pub struct Word<F: FieldExt>(Vec<AssignedCell<F, F>>);
+
+impl<F: FieldExt> Word<F> {
+    fn value(&self) -> Value<u64> {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(Value::known(0), |acc, (i, bit)| {
+                acc.zip(bit.value())
+                    .map(|(acc, bit)| acc | (u64::from(*bit == F::one()) << i))
+            })
+    }
+
+    fn rotr(&self, n: usize) -> Vec<Option<&AssignedCell<F, F>>> {
+        (0..WORD_BITS)
+            .map(|i| Some(&self.0[(i + n) % WORD_BITS]))
+            .collect()
+    }
+
+    /// Bits of the word shifted right by `n`, `None` standing for a zero.
+    fn shr(&self, n: usize) -> Vec<Option<&AssignedCell<F, F>>> {
+        (0..WORD_BITS).map(|i| self.0.get(i + n)).collect()
+    }
+}
+
+/// A term of [`TwoColumnChip::linear`].
+enum Term<'a, F: FieldExt> {
+    /// A cell assigned before.
+    Copy(&'a AssignedCell<F, F>),
+    /// A new bit.
+    Bit(Value<F>),
+}
+
+type BitFn<F> = fn(F, F, F) -> F;
+
+#[derive(Clone, Debug)]
+pub struct TwoColumnChip<F: FieldExt> {
+    config: TwoColumnConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> TwoColumnChip<F> {
+    pub fn construct(config: TwoColumnConfig) -> Self {
+        Self {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> TwoColumnConfig {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let coeff = meta.fixed_column();
+        let constants = meta.fixed_column();
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_constant(constants);
+
+        let config = TwoColumnConfig {
+            a,
+            b,
+            coeff,
+            q_bool: meta.selector(),
+            q_lin: meta.selector(),
+            q_xor: meta.selector(),
+            q_ch: meta.selector(),
+            q_maj: meta.selector(),
+        };
+
+        let one = || Expression::Constant(F::one());
+        meta.create_gate("bool", |meta| {
+            let q = meta.query_selector(config.q_bool);
+            let a = meta.query_advice(a, Rotation::cur());
+            vec![q * a.clone() * (one() - a)]
+        });
+        meta.create_gate("linear combination", |meta| {
+            let q = meta.query_selector(config.q_lin);
+            let acc = meta.query_advice(b, Rotation::cur());
+            let next = meta.query_advice(b, Rotation::next());
+            let term = meta.query_advice(a, Rotation::next());
+            let coeff = meta.query_fixed(coeff, Rotation::next());
+            vec![q * (next - acc - coeff * term)]
+        });
+
+        let bit_gate =
+            |meta: &mut ConstraintSystem<F>,
+             name: &'static str,
+             selector: Selector,
+             f: fn(Expression<F>, Expression<F>, Expression<F>) -> Expression<F>| {
+                meta.create_gate(name, |meta| {
+                    let q = meta.query_selector(selector);
+                    let x = meta.query_advice(a, Rotation::cur());
+                    let y = meta.query_advice(b, Rotation::cur());
+                    let z = meta.query_advice(a, Rotation::next());
+                    let out = meta.query_advice(b, Rotation::next());
+                    vec![q * (out - f(x, y, z))]
+                });
+            };
+        bit_gate(meta, "xor", config.q_xor, |x, y, z| {
+            let two = Expression::Constant(F::from(2));
+            let four = Expression::Constant(F::from(4));
+            x.clone() + y.clone() + z.clone()
+                - two * (x.clone() * y.clone() + x.clone() * z.clone() + y.clone() * z.clone())
+                + four * x * y * z
+        });
+        bit_gate(meta, "ch", config.q_ch, |x, y, z| {
+            x.clone() * y + (Expression::Constant(F::one()) - x) * z
+        });
+        bit_gate(meta, "maj", config.q_maj, |x, y, z| {
+            let two = Expression::Constant(F::from(2));
+            x.clone() * y.clone() + x.clone() * z.clone() + y.clone() * z.clone() - two * x * y * z
+        });
+
+        config
+    }
+
+    /// Lays out `start + sum(coeff * term)` down column `b`, one term a row in column
+    /// `a`, and constrains it to `end` if given. Returns the cells of the terms and the
+    /// sum.
+    #[allow(clippy::type_complexity)]
+    fn linear(
+        &self,
+        mut layouter: impl Layouter<F>,
+        start: F,
+        terms: &[(Term<F>, F)],
+        end: Option<F>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, AssignedCell<F, F>), Error> {
+        let config = &self.config;
+        layouter.assign_region(
+            || "linear combination",
+            |mut region| {
+                let mut acc = region.assign_advice_from_constant(|| "start", config.b, 0, start)?;
+                let mut cells = Vec::with_capacity(terms.len());
+                for (i, (term, coeff)) in terms.iter().enumerate() {
+                    let row = i + 1;
+                    config.q_lin.enable(&mut region, i)?;
+                    region.assign_fixed(|| "coeff", config.coeff, row, || Value::known(*coeff))?;
+                    let cell = match term {
+                        Term::Copy(cell) => {
+                            cell.copy_advice(|| "term", &mut region, config.a, row)?
+                        }
+                        Term::Bit(bit) => {
+                            config.q_bool.enable(&mut region, row)?;
+                            region.assign_advice(|| "bit", config.a, row, || *bit)?
+                        }
+                    };
+                    let value = acc.value().copied() + cell.value().map(|term| *term * coeff);
+                    acc = region.assign_advice(|| "sum", config.b, row, || value)?;
+                    cells.push(cell);
+                }
+                if let Some(end) = end {
+                    region.constrain_constant(acc.cell(), end)?;
+                }
+                Ok((cells, acc))
+            },
+        )
+    }
+
+    /// Witnesses a word, returning its bits and the cell of its value.
+    pub fn witness_word(
+        &self,
+        layouter: impl Layouter<F>,
+        word: Value<u32>,
+    ) -> Result<(Word<F>, AssignedCell<F, F>), Error> {
+        let terms = (0..WORD_BITS)
+            .map(|i| {
+                (
+                    Term::Bit(word.map(|word| F::from(u64::from(word >> i & 1)))),
+                    pow2(i),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (bits, value) = self.linear(layouter, F::zero(), &terms, None)?;
+        Ok((Word(bits), value))
+    }
+
+    /// The cell of the value of `word`.
+    pub fn compose(
+        &self,
+        layouter: impl Layouter<F>,
+        word: &Word<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let terms = word
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| (Term::Copy(bit), pow2(i)))
+            .collect::<Vec<_>>();
+        Ok(self.linear(layouter, F::zero(), &terms, None)?.1)
+    }
+
+    pub fn constant_word(
+        &self,
+        mut layouter: impl Layouter<F>,
+        word: u32,
+    ) -> Result<Word<F>, Error> {
+        let config = &self.config;
+        layouter.assign_region(
+            || "constant word",
+            |mut region| {
+                let bits = (0..WORD_BITS)
+                    .map(|i| {
+                        let bit = F::from(u64::from(word >> i & 1));
+                        region.assign_advice_from_constant(|| "bit", config.a, i, bit)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Word(bits))
+            },
+        )
+    }
+
+    /// `constant + sum(words)` modulo `2^32`.
+    fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        words: &[&Word<F>],
+        constant: u32,
+    ) -> Result<Word<F>, Error> {
+        assert!(words.len() < 1 << CARRY_BITS);
+        let sum = words
+            .iter()
+            .fold(Value::known(u64::from(constant)), |sum, word| {
+                sum + word.value()
+            });
+        let bit = |i: usize| Term::Bit(sum.map(|sum| F::from(sum >> i & 1)));
+
+        let mut terms = Vec::with_capacity((words.len() + 1) * WORD_BITS + CARRY_BITS);
+        for word in words {
+            terms.extend(
+                word.0
+                    .iter()
+                    .enumerate()
+                    .map(|(i, bit)| (Term::Copy(bit), pow2(i))),
+            );
+        }
+        terms.extend((0..WORD_BITS + CARRY_BITS).map(|i| (bit(i), -pow2(i))));
+
+        let (mut cells, _) = self.linear(
+            layouter,
+            F::from(u64::from(constant)),
+            &terms,
+            Some(F::zero()),
+        )?;
+        let out = cells.split_off(words.len() * WORD_BITS);
+        Ok(Word(out[..WORD_BITS].to_vec()))
+    }
+
+    /// Applies `f` bit by bit, `selector` being the gate of `f`.
+    fn bitwise(
+        &self,
+        mut layouter: impl Layouter<F>,
+        selector: Selector,
+        f: BitFn<F>,
+        [x, y, z]: [&[Option<&AssignedCell<F, F>>]; 3],
+    ) -> Result<Word<F>, Error> {
+        let config = &self.config;
+        layouter.assign_region(
+            || "bitwise",
+            |mut region| {
+                let mut assign = |bit: Option<&AssignedCell<F, F>>, column, row| match bit {
+                    Some(bit) => bit.copy_advice(|| "bit", &mut region, column, row),
+                    None => region.assign_advice_from_constant(|| "zero", column, row, F::zero()),
+                };
+                let mut inputs = Vec::with_capacity(WORD_BITS);
+                for i in 0..WORD_BITS {
+                    let x = assign(x[i], config.a, 2 * i)?;
+                    let y = assign(y[i], config.b, 2 * i)?;
+                    let z = assign(z[i], config.a, 2 * i + 1)?;
+                    inputs.push((x, y, z));
+                }
+
+                let bits = inputs
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (x, y, z))| {
+                        selector.enable(&mut region, 2 * i)?;
+                        let value = x
+                            .value()
+                            .zip(y.value())
+                            .zip(z.value())
+                            .map(|((x, y), z)| f(*x, *y, *z));
+                        region.assign_advice(|| "out", config.b, 2 * i + 1, || value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Word(bits))
+            },
+        )
+    }
+
+    fn xor(
+        &self,
+        layouter: impl Layouter<F>,
+        inputs: [&[Option<&AssignedCell<F, F>>]; 3],
+    ) -> Result<Word<F>, Error> {
+        self.bitwise(
+            layouter,
+            self.config.q_xor,
+            |x, y, z| x + y + z - (x * y + x * z + y * z).double() + (x * y * z).double().double(),
+            inputs,
+        )
+    }
+
+    /// Hashes one block, the compression function of SHA256.
+    pub fn compress(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &[Word<F>],
+        block: &[Word<F>],
+    ) -> Result<Vec<Word<F>>, Error> {
+        let mut w = block.to_vec();
+        for t in 16..64 {
+            let s0 = {
+                let x = &w[t - 15];
+                self.xor(
+                    layouter.namespace(|| "σ0"),
+                    [&x.rotr(7), &x.rotr(18), &x.shr(3)],
+                )?
+            };
+            let s1 = {
+                let x = &w[t - 2];
+                self.xor(
+                    layouter.namespace(|| "σ1"),
+                    [&x.rotr(17), &x.rotr(19), &x.shr(10)],
+                )?
+            };
+            let word = self.add(
+                layouter.namespace(|| "schedule"),
+                &[&s1, &w[t - 7], &s0, &w[t - 16]],
+                0,
+            )?;
+            w.push(word);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [Word<F>; 8] =
+            state.to_vec().try_into().expect("the state has 8 words");
+        for (t, w) in w.iter().enumerate() {
+            let s1 = self.xor(
+                layouter.namespace(|| "Σ1"),
+                [&e.rotr(6), &e.rotr(11), &e.rotr(25)],
+            )?;
+            let ch = self.bitwise(
+                layouter.namespace(|| "ch"),
+                self.config.q_ch,
+                |x, y, z| x * y + (F::one() - x) * z,
+                [&e.rotr(0), &f.rotr(0), &g.rotr(0)],
+            )?;
+            let s0 = self.xor(
+                layouter.namespace(|| "Σ0"),
+                [&a.rotr(2), &a.rotr(13), &a.rotr(22)],
+            )?;
+            let maj = self.bitwise(
+                layouter.namespace(|| "maj"),
+                self.config.q_maj,
+                |x, y, z| x * y + x * z + y * z - (x * y * z).double(),
+                [&a.rotr(0), &b.rotr(0), &c.rotr(0)],
+            )?;
+
+            let k = ROUND_CONSTANTS[t];
+            let new_e = self.add(layouter.namespace(|| "e"), &[&d, &h, &s1, &ch, w], k)?;
+            let new_a = self.add(layouter.namespace(|| "a"), &[&h, &s1, &ch, w, &s0, &maj], k)?;
+            h = g;
+            g = f;
+            f = e;
+            e = new_e;
+            d = c;
+            c = b;
+            b = a;
+            a = new_a;
+        }
+
+        state
+            .iter()
+            .zip([a, b, c, d, e, f, g, h])
+            .map(|(state, word)| self.add(layouter.namespace(|| "state"), &[state, &word], 0))
+            .collect()
+    }
+}
+
+fn pow2<F: FieldExt>(i: usize) -> F {
+    F::from(2).pow_vartime([i as u64])
+}
+
+/// [`ChainHasher`] of [`TwoColumnChip`]. The preimage and the digests are the cells of
+/// the hash function, so the chain is constrained end to end.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TwoColumn;
+
+impl<F: FieldExt> ChainHasher<F> for TwoColumn {
+    const NAME: &'static str = "two-column";
+    const SOUND: bool = true;
+
+    type Config = TwoColumnConfig;
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        TwoColumnChip::configure(meta)
+    }
+
+    fn hash_chain(
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+        preimage: Value<[u32; 8]>,
+        iter_num: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        let chip = TwoColumnChip::construct(config);
+
+        let mut public = Vec::with_capacity(16);
+        let mut digest = Vec::with_capacity(8);
+        for i in 0..8 {
+            let (word, value) = chip.witness_word(
+                layouter.namespace(|| "preimage"),
+                preimage.map(|preimage| preimage[i]),
+            )?;
+            digest.push(word);
+            public.push(value);
+        }
+        let iv = IV
+            .iter()
+            .map(|&word| chip.constant_word(layouter.namespace(|| "iv"), word))
+            .collect::<Result<Vec<_>, _>>()?;
+        let padding = PADDING
+            .iter()
+            .map(|&word| chip.constant_word(layouter.namespace(|| "padding"), word))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for _ in 0..iter_num {
+            let block = [digest.as_slice(), padding.as_slice()].concat();
+            digest = chip.compress(layouter.namespace(|| "sha256"), &iv, &block)?;
+        }
+        for word in &digest {
+            public.push(chip.compose(layouter.namespace(|| "digest"), word)?);
+        }
+        Ok(public)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{
+        circuit::SimpleFloorPlanner,
+        dev::MockProver,
+        halo2curves::bn256::Fr,
+        plonk::{Circuit, Instance},
+    };
+    use nova_bench::sha256::sha256_chain_digest;
+
+    use super::*;
+    use crate::{
+        circuit::{chain_instance, Sha256Chain},
+        params::circuit_size,
+    };
+
+    /// Two hashes of `preimage` linked like in [`TwoColumn::hash_chain`], by copy
+    /// constraints on the bits of the first digest, except that the prover witnesses
+    /// the second block itself and flips a bit of it if `tamper` is set. The digest is
+    /// then that of another chain, spliced onto the first hash.
+    #[derive(Clone, Default)]
+    struct SplicedChain {
+        preimage: [u32; 8],
+        tamper: bool,
+    }
+
+    impl Circuit<Fr> for SplicedChain {
+        type Config = (TwoColumnConfig, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            (TwoColumnChip::configure(meta), instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let chip = TwoColumnChip::construct(config.clone());
+            let mut public = Vec::new();
+            let mut preimage = Vec::new();
+            for word in self.preimage {
+                let (word, value) =
+                    chip.witness_word(layouter.namespace(|| "preimage"), Value::known(word))?;
+                preimage.push(word);
+                public.push(value);
+            }
+            let iv = IV
+                .iter()
+                .map(|&word| chip.constant_word(layouter.namespace(|| "iv"), word))
+                .collect::<Result<Vec<_>, _>>()?;
+            let padding = PADDING
+                .iter()
+                .map(|&word| chip.constant_word(layouter.namespace(|| "padding"), word))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let block = [preimage.as_slice(), padding.as_slice()].concat();
+            let digest = chip.compress(layouter.namespace(|| "sha256"), &iv, &block)?;
+            let linked = layouter.assign_region(
+                || "link",
+                |mut region| {
+                    let mut row = 0;
+                    let mut linked = Vec::new();
+                    for (i, word) in digest.iter().enumerate() {
+                        let mut bits = Vec::new();
+                        for (j, bit) in word.0.iter().enumerate() {
+                            let flip = self.tamper && i == 0 && j == 0;
+                            let value = bit
+                                .value()
+                                .map(|&bit| if flip { Fr::from(1) - bit } else { bit });
+                            let cell = region.assign_advice(|| "bit", config.a, row, || value)?;
+                            region.constrain_equal(bit.cell(), cell.cell())?;
+                            bits.push(cell);
+                            row += 1;
+                        }
+                        linked.push(Word(bits));
+                    }
+                    Ok(linked)
+                },
+            )?;
+
+            let block = [linked.as_slice(), padding.as_slice()].concat();
+            let digest = chip.compress(layouter.namespace(|| "sha256"), &iv, &block)?;
+            for word in &digest {
+                public.push(chip.compose(layouter.namespace(|| "digest"), word)?);
+            }
+            for (i, cell) in public.iter().enumerate() {
+                layouter.constrain_instance(cell.cell(), instance, i)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn digest_matches_native_sha256() {
+        let circuit = Sha256Chain::<TwoColumn>::new([0; 32], 1);
+        let digest = sha256_chain_digest([0; 32], 1);
+
+        let instance = chain_instance::<Fr>(&[0; 32], &digest);
+        let prover = MockProver::run(17, &circuit, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let wrong = sha256_chain_digest([0; 32], 2);
+        let instance = chain_instance::<Fr>(&[0; 32], &wrong);
+        let prover = MockProver::run(17, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn rejects_a_spliced_chain() {
+        let honest = SplicedChain::default();
+        let instance = chain_instance::<Fr>(&[0; 32], &sha256_chain_digest([0; 32], 2));
+        let k = circuit_size::<Fr, _>(&honest).min_k;
+        let prover = MockProver::run(k, &honest, vec![instance]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // The instance is what the tampered chain does hash to, so only the link
+        // between the two hashes can catch it.
+        let mut spliced = sha256_chain_digest([0; 32], 1);
+        spliced[3] ^= 1;
+        let instance = chain_instance::<Fr>(&[0; 32], &sha256_chain_digest(spliced, 1));
+        let tampered = SplicedChain {
+            tamper: true,
+            ..honest
+        };
+        let prover = MockProver::run(k, &tampered, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -1,3 +1,5 @@
+set -e
+
 cd examples/bitcoin/circom
 # ghead on OSX, head on Linux
 head -n -1 bitcoin.circom > bitcoin_benchmark.circom
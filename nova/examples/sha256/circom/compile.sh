@@ -0,0 +1,48 @@
+#!/bin/bash
+
+# Usage: ./examples/sha256/circom/compile.sh [depth_per_fold] [circuit] [cpp|wasm] [vesta|pallas]
+#
+# Renders `Main(depth_per_fold)` of `circuit` (sha256_test_nova or
+# sha256_test_nova_checked) and compiles it into build/d<depth_per_fold>, along with
+# the given witness generator (wasm by default). The sha256 examples call this
+# themselves when the artifacts are missing. With `pallas`, the field of the
+# secondary circuit used by `--mode dual`, it goes to build/d<depth_per_fold>/pallas.
+
+set -e
+
+DEPTH_PER_FOLD=${1:-10}
+CIRCUIT=${2:-sha256_test_nova}
+WITNESS=${3:-wasm}
+PRIME=${4:-vesta}
+CIRCUIT_DIR=./examples/sha256/circom
+BUILD_DIR=${CIRCUIT_DIR}/build/d${DEPTH_PER_FOLD}
+
+case "$WITNESS" in
+  cpp) WITNESS_FLAG=--c ;;
+  wasm) WITNESS_FLAG=--wasm ;;
+  *) echo "Unknown witness generator $WITNESS, expected cpp or wasm" >&2; exit 1 ;;
+esac
+
+case "$PRIME" in
+  vesta) ;;
+  pallas) BUILD_DIR=${BUILD_DIR}/pallas ;;
+  *) echo "Unknown prime $PRIME, expected vesta or pallas" >&2; exit 1 ;;
+esac
+
+mkdir -p "$BUILD_DIR"
+sed "s/Main([0-9]*)/Main($DEPTH_PER_FOLD)/" ${CIRCUIT_DIR}/${CIRCUIT}.circom > "$BUILD_DIR"/${CIRCUIT}.circom
+
+# Nova Scotia's F1 is the scalar field of Pallas, which circom calls vesta, and F2
+# that of Vesta, i.e. circom's pallas
+circom "$BUILD_DIR"/${CIRCUIT}.circom -l "$CIRCUIT_DIR" --r1cs --sym $WITNESS_FLAG --output "$BUILD_DIR" --prime $PRIME
+
+if [ "$WITNESS" = "wasm" ]; then
+  # NOTE: This is just one step of the computation
+  # Full computation happens inside the sha256 examples
+  if [ "$CIRCUIT" = "sha256_test_nova" ] && [ "$PRIME" = "vesta" ]; then
+    (cd "$BUILD_DIR"/${CIRCUIT}_js && node generate_witness.js ${CIRCUIT}.wasm ../../../input_32_first_step.json output.wtns)
+  fi
+else
+  # Doesn't work on M1, use wasm instead
+  (cd "$BUILD_DIR"/${CIRCUIT}_cpp && make)
+fi
@@ -19,29 +19,7 @@
 
 pragma circom 2.0.3;
 
-include "sha256_bytes.circom";
-
-template RecursiveShaTest(N, depth) {
-
-    signal input in[N];
-    signal input hash[32]; // XXX Not using this check
-    signal output out[32];
-
-    signal value[depth+1][N];
-
-    component hasher[depth];
-
-    value[0] <== in;
-
-    for (var i = 0; i < depth; i++) {
-        hasher[i] = Sha256Bytes(N);
-        hasher[i].in <== value[i];
-
-        value[i+1] <== hasher[i].out;
-    }
-
-    out <== value[depth];
-}
+include "sha256_chain.circom";
 
 template Main(depth_per_fold) {
     signal input in[32];
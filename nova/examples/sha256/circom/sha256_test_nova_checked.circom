@@ -0,0 +1,50 @@
+/*
+    Copyright 2018 0KIMS association.
+
+    This file is part of circom (Zero Knowledge Circuit Compiler).
+
+    circom is a free software: you can redistribute it and/or modify it
+    under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    circom is distributed in the hope that it will be useful, but WITHOUT
+    ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public
+    License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with circom. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+pragma circom 2.0.3;
+
+include "sha256_chain.circom";
+
+// Same as sha256_test_nova.circom, but the private inputs are constrained like in
+// circom/circuits/sha256_test/sha256_test.circom.
+template Main(depth_per_fold) {
+    signal input in[32];
+    signal input hash[32];
+    signal input step_in[32];
+    signal output step_out[32];
+
+    // The private preimage is the running hash
+    for (var i = 0; i < 32; i++) {
+        in[i] === step_in[i];
+    }
+
+    component chainedSha = RecursiveShaTest(32, depth_per_fold);
+    chainedSha.in <== in;
+    chainedSha.hash <== hash;
+
+    // The final output should be same as the inputed hash
+    for (var i = 0; i < 32; i++) {
+        hash[i] === chainedSha.out[i];
+    }
+
+    step_out <== chainedSha.out;
+}
+
+// render this file before compilation
+component main { public [step_in] } = Main(10);
\ No newline at end of file
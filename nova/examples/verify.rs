@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use nova::{bytes_to_field, field_to_bytes, proof::ProofFile, sha256::gen_nth_sha256_hash};
+use nova_bench::{proof::ProofMeta, report::OutputArgs};
+
+#[derive(Debug, Parser)]
+#[command(about = "Verify a Nova proof written with --proof-dir")]
+struct Args {
+    /// Directory the proof was written to.
+    proof_dir: PathBuf,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let args = Args::parse();
+    let dir = &args.proof_dir;
+
+    let meta = ProofMeta::read(dir)
+        .unwrap_or_else(|e| panic!("failed to read the metadata in {}: {e}", dir.display()));
+    let mut record = meta.verifier_record(dir);
+
+    let (proof_file, vk) = record.time("load", || {
+        let proof_file = ProofFile::read(dir).expect("failed to read the proof");
+        let vk = proof_file
+            .read_vk(dir)
+            .expect("failed to read the verifying key");
+        (proof_file, vk)
+    });
+
+    // The proof only shows that `num_steps` folds lead from `z0` to `z_n`, all of which
+    // the prover picked. Check them against the chain of `k` hashes of 0^32 itself.
+    let d = meta.d.expect("the metadata has no d");
+    assert_eq!(
+        proof_file.num_steps * d,
+        meta.k,
+        "the proof has {} folds of {d} hashes, not {}",
+        proof_file.num_steps,
+        meta.k
+    );
+    assert_eq!(
+        proof_file.z0,
+        bytes_to_field(&[0; 32]),
+        "the proof doesn't start from 0^32"
+    );
+
+    let zn = record
+        .time("verify", || proof_file.verify(&vk))
+        .unwrap_or_else(|e| panic!("{} proof verification failed: {e}", meta.mode));
+    assert_eq!(
+        field_to_bytes(&zn),
+        Some(gen_nth_sha256_hash(meta.k)),
+        "z_n isn't the digest of {} recursive hashes of 0^32",
+        meta.k
+    );
+    println!(
+        "Verified the {} proof of {} folds, {} recursive hashes of 0^32",
+        meta.mode, proof_file.num_steps, meta.k
+    );
+
+    args.output.emit(&record);
+}
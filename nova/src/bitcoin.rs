@@ -0,0 +1,136 @@
+//! Verification of a chain of Bitcoin block headers, `per_iteration_count` blocks per fold.
+
+use std::{env::current_dir, path::PathBuf, process::Command};
+
+use ff::PrimeField;
+use nova_bench::report::BenchRecord;
+use nova_scotia::F1;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{NovaBenchmark, PrivateInput, WitnessGenerator};
+
+const CIRCUIT_NAME: &str = "bitcoin_benchmark";
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Blocks {
+    pub prevBlockHash: [String; 2],
+    pub blockHashes: Vec<[String; 2]>,
+    pub blockHeaders: Vec<Vec<u8>>,
+}
+
+impl Blocks {
+    pub fn load() -> Self {
+        serde_json::from_str(include_str!("../examples/bitcoin/fetcher/btc-blocks.json")).unwrap()
+    }
+}
+
+pub struct BitcoinBlocks {
+    blocks: Blocks,
+    pub iteration_count: usize,
+    pub per_iteration_count: usize,
+}
+
+impl BitcoinBlocks {
+    pub fn new(iteration_count: usize, per_iteration_count: usize) -> Self {
+        Self {
+            blocks: Blocks::load(),
+            iteration_count,
+            per_iteration_count,
+        }
+    }
+
+    /// Empty result record describing this run, `k` being the number of blocks.
+    pub fn record(&self, mode: &str) -> BenchRecord {
+        let mut record = BenchRecord::new(
+            "nova",
+            mode,
+            self.iteration_count * self.per_iteration_count,
+        );
+        record.d = Some(self.per_iteration_count);
+        record.set_param("workload", "bitcoin");
+        record.set_param("steps", self.iteration_count);
+        record
+    }
+
+    /// Renders and compiles the step circuit for `per_iteration_count` blocks per fold.
+    pub fn compile(&self) {
+        let status = Command::new("bash")
+            .arg("examples/bitcoin/circom/compile.sh")
+            .arg(self.per_iteration_count.to_string())
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success(), "failed to compile the step circuit");
+    }
+
+    fn circuit_dir(&self) -> PathBuf {
+        current_dir().unwrap().join("examples/bitcoin/circom")
+    }
+}
+
+impl NovaBenchmark for BitcoinBlocks {
+    fn r1cs_file(&self) -> PathBuf {
+        self.circuit_dir().join(format!("{CIRCUIT_NAME}.r1cs"))
+    }
+
+    fn witness_generator_file(&self) -> PathBuf {
+        WitnessGenerator::Cpp.path(&self.circuit_dir(), CIRCUIT_NAME)
+    }
+
+    fn start_public_input(&self) -> Vec<F1> {
+        vec![
+            F1::from_str_vartime(&self.blocks.prevBlockHash[0]).unwrap(),
+            F1::from_str_vartime(&self.blocks.prevBlockHash[1]).unwrap(),
+        ]
+    }
+
+    fn check_output(&self, zn: &[F1]) {
+        let last_block =
+            &self.blocks.blockHashes[self.iteration_count * self.per_iteration_count - 1];
+        let expected = last_block
+            .iter()
+            .map(|x| F1::from_str_vartime(x).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            zn, expected,
+            "lastBlockHash doesn't match the block headers"
+        );
+    }
+
+    /// `z_0` is the hash of the block before the first one, after that the hash of the
+    /// last block of the previous fold.
+    fn step_public_inputs(&self) -> Vec<Vec<F1>> {
+        let per = self.per_iteration_count;
+        (0..self.iteration_count)
+            .map(|i| {
+                let hash = if i == 0 {
+                    &self.blocks.prevBlockHash
+                } else {
+                    &self.blocks.blockHashes[i * per - 1]
+                };
+                hash.iter()
+                    .map(|x| F1::from_str_vartime(x).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn private_inputs(&self) -> Vec<PrivateInput> {
+        let per = self.per_iteration_count;
+        (0..self.iteration_count)
+            .map(|i| {
+                let mut private_input = PrivateInput::new();
+                private_input.insert(
+                    "blockHashes".to_string(),
+                    json!(self.blocks.blockHashes[i * per..i * per + per]),
+                );
+                private_input.insert(
+                    "blockHeaders".to_string(),
+                    json!(self.blocks.blockHeaders[i * per..i * per + per]),
+                );
+                private_input
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,76 @@
+//! On-disk cache of the public params and the Spartan prover/verifier keys.
+//!
+//! Entries are keyed by the SHA256 of the `.r1cs` file and the curve cycle, so
+//! recompiling a circuit with a different `depth_per_fold` naturally misses. The
+//! cache doesn't know which Nova version wrote an entry: clear it after bumping
+//! `nova-snark`.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Curve cycle of `nova_scotia::{G1, G2}`.
+const CURVE: &str = "pallas-vesta";
+
+#[derive(Clone, Debug)]
+pub struct ParamsCache {
+    dir: PathBuf,
+}
+
+impl ParamsCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn entry_path(&self, r1cs_file: &Path, name: &str) -> PathBuf {
+        let r1cs = fs::read(r1cs_file)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", r1cs_file.display()));
+        let key = Sha256::digest(&r1cs);
+        self.dir.join(format!("{key:x}-{CURVE}-{name}.bin"))
+    }
+
+    /// Loads entry `name` for `r1cs_file`, or creates it with `create` and stores it.
+    /// Returns whether it was a hit along with the value.
+    pub fn load_or_create<T: Serialize + DeserializeOwned>(
+        &self,
+        r1cs_file: &Path,
+        name: &str,
+        create: impl FnOnce() -> T,
+    ) -> (T, bool) {
+        let path = self.entry_path(r1cs_file, name);
+
+        if let Ok(file) = File::open(&path) {
+            match bincode::deserialize_from(BufReader::new(file)) {
+                Ok(value) => return (value, true),
+                Err(e) => println!("Ignoring unreadable cache entry {}: {e}", path.display()),
+            }
+        }
+
+        let value = create();
+        if let Err(e) = self.store(&path, &value) {
+            println!("Failed to cache {}: {e}", path.display());
+        }
+        (value, false)
+    }
+
+    fn store<T: Serialize>(
+        &self,
+        path: &Path,
+        value: &T,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.dir)?;
+        // Write to a temporary file first so that an interrupted run can't leave a
+        // truncated entry behind.
+        let tmp = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        bincode::serialize_into(&mut writer, value)?;
+        writer.flush()?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
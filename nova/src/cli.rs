@@ -0,0 +1,127 @@
+//! Command-line arguments and driver shared by the SHA256 examples.
+
+use std::path::PathBuf;
+
+use clap::{builder::RangedU64ValueParser, error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use nova_bench::report::OutputArgs;
+
+use crate::{cache::ParamsCache, run, run_par, sha256::Sha256Chain, RunOptions, WitnessGenerator};
+
+/// Which Nova prover to benchmark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Sequential `RecursiveSNARK`, followed by compression.
+    Seq,
+    /// Parallel tree prover (PoC).
+    Par,
+    /// Primary and secondary circuit both hashing, each half of the chain, see
+    /// [`crate::dual`]. Needs the `dual` feature.
+    Dual,
+}
+
+impl Mode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Seq => "seq",
+            Mode::Par => "par",
+            Mode::Dual => "dual",
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Recursive SHA256 hashing with Nova")]
+pub struct Sha256Args {
+    #[arg(long, value_enum, default_value_t = Mode::Seq)]
+    pub mode: Mode,
+
+    /// Number of folds. Each fold does `depth_per_fold` hashes.
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub steps: usize,
+
+    /// Number of hashes inside each fold, the step circuit is compiled for it if needed.
+    #[arg(long, default_value_t = 10, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub depth_per_fold: usize,
+
+    /// Constrain the private `in`/`hash` inputs of every fold, like the Circom baseline does.
+    #[arg(long)]
+    pub checked: bool,
+
+    /// Witness generator, defaults to `cpp` for `sha256` and `wasm` for `sha256_wasm`.
+    #[arg(long, value_enum)]
+    pub witness: Option<WitnessGenerator>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Directory in which the public params and Spartan keys are cached across runs.
+    #[arg(long, default_value = "params_cache")]
+    pub params_cache: PathBuf,
+
+    /// Always regenerate the public params and Spartan keys.
+    #[arg(long)]
+    pub no_params_cache: bool,
+
+    /// Write the proof and its verifying key to this directory, see `examples/verify.rs`.
+    #[arg(long)]
+    pub proof_dir: Option<PathBuf>,
+}
+
+impl Sha256Args {
+    pub fn params_cache(&self) -> Option<ParamsCache> {
+        (!self.no_params_cache).then(|| ParamsCache::new(&self.params_cache))
+    }
+
+    /// Compiles the step circuit if needed, runs the benchmark and emits its record.
+    /// `witness` is the generator used unless `--witness` is given.
+    pub fn run_benchmark(&self, witness: WitnessGenerator) {
+        if self.mode == Mode::Dual {
+            self.check_dual_args();
+        }
+        let bench = Sha256Chain::new(
+            self.steps,
+            self.depth_per_fold,
+            self.witness.unwrap_or(witness),
+            self.checked,
+        );
+        println!(
+            "Using {} folds with depth_per_fold = {} ({} recursive hashes)",
+            bench.num_steps,
+            bench.depth_per_fold,
+            bench.num_hashes()
+        );
+        bench.compile();
+
+        let options = RunOptions {
+            compress: true,
+            params_cache: self.params_cache(),
+            proof_dir: self.proof_dir.clone(),
+        };
+        let mut record = bench.record(self.mode.name());
+        match self.mode {
+            Mode::Seq => run(&bench, &options, &mut record),
+            Mode::Par => run_par(&bench, &options, &mut record),
+            #[cfg(feature = "dual")]
+            Mode::Dual => crate::dual::run_dual(&bench, &mut record),
+            #[cfg(not(feature = "dual"))]
+            Mode::Dual => unreachable!("rejected by check_dual_args"),
+        }
+        self.output.emit(&record);
+    }
+
+    /// Exits with a usage error if `--mode dual` can't run with the other arguments.
+    fn check_dual_args(&self) {
+        let error = if !cfg!(feature = "dual") {
+            "--mode dual needs the `dual` feature and the feat/r1cs-dual nova-scotia branch"
+        } else if self.checked {
+            "--checked can't be used with --mode dual"
+        } else if self.proof_dir.is_some() {
+            "--proof-dir can't be used with --mode dual"
+        } else {
+            return;
+        };
+        Self::command()
+            .error(ErrorKind::ArgumentConflict, error)
+            .exit()
+    }
+}
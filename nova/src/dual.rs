@@ -0,0 +1,121 @@
+//! Dual mode: the primary and the secondary circuit of Nova both run the SHA256 step
+//! circuit instead of the secondary one being trivial. The primary circuit hashes the
+//! first half of the chain, from `0^32`, and the secondary one the second half, from
+//! its midpoint, so every fold does `2 * depth_per_fold` hashes.
+//!
+//! Needs `create_public_params_alt` and `create_recursive_circuit_alt` from the
+//! `feat/r1cs-dual` branch of nova-scotia, hence the `dual` feature. The secondary
+//! R1CS is compiled over circom's `pallas`, the field of `F2`. Both circuits share the
+//! witness generator compiled over `vesta`: the witness of the SHA256 circuit is made
+//! of bits and small sums, which are the same in both fields.
+
+use ark_std::{end_timer, start_timer};
+use nova_bench::report::BenchRecord;
+use nova_scotia::{
+    circom::reader::load_r1cs, create_public_params_alt, create_recursive_circuit_alt,
+    FileLocation, F2, G1, G2, S1, S2,
+};
+use nova_snark::CompressedSNARK;
+
+use crate::{
+    bytes_to_field, print_shape, serialized_size,
+    sha256::{gen_nth_sha256_hash, Sha256Chain},
+    NovaBenchmark,
+};
+
+/// Same as [`bytes_to_field`], in the field of the secondary circuit.
+fn bytes_to_secondary_field(bytes: &[u8]) -> Vec<F2> {
+    bytes.iter().map(|&x| F2::from(x as u64)).collect()
+}
+
+/// Folds `bench.num_steps` steps of both circuits, i.e. `2 * bench.num_hashes()`
+/// hashes, then compresses the `RecursiveSNARK`. The outputs of both circuits are
+/// checked against the native chain. Phases, circuit shape and proof size go into
+/// `record` like in [`crate::run`], with `k` set to the hashes of both circuits.
+pub fn run_dual(bench: &Sha256Chain, record: &mut BenchRecord) {
+    assert!(
+        !bench.checked,
+        "the checked circuit can't start the secondary chain from its midpoint"
+    );
+    bench.compile_secondary();
+
+    let num_steps = bench.num_steps;
+    let num_hashes = bench.num_hashes();
+    record.k = 2 * num_hashes;
+    println!("Dual mode: {} recursive hashes in total", record.k);
+
+    let r1cs_primary = load_r1cs::<G1>(&FileLocation::PathBuf(bench.r1cs_file()));
+    let r1cs_secondary = load_r1cs::<G2>(&FileLocation::PathBuf(bench.secondary_r1cs_file()));
+    let z0_primary = bench.start_public_input();
+    let z0_secondary = bytes_to_secondary_field(&gen_nth_sha256_hash(num_hashes));
+
+    let pp = record.time("setup", || {
+        create_public_params_alt(r1cs_primary.clone(), r1cs_secondary.clone())
+    });
+    print_shape(pp.num_constraints(), pp.num_variables());
+    record.constraints = Some(pp.num_constraints().0);
+    record.variables = Some(pp.num_variables().0);
+    record.set_param("secondary_constraints", pp.num_constraints().1);
+    record.set_param("secondary_variables", pp.num_variables().1);
+
+    let timer_create_proof = start_timer!(|| "Create RecursiveSNARK");
+    let recursive_snark = record.time("prove", || {
+        create_recursive_circuit_alt(
+            FileLocation::PathBuf(bench.witness_generator_file()),
+            r1cs_primary,
+            r1cs_secondary,
+            bench.private_inputs(),
+            z0_primary.clone(),
+            z0_secondary.clone(),
+            &pp,
+        )
+        .expect("failed to fold the steps")
+    });
+    end_timer!(timer_create_proof);
+
+    let check_outputs = |(zn_primary, zn_secondary): (Vec<_>, Vec<_>)| {
+        assert_eq!(
+            zn_primary,
+            bytes_to_field(&gen_nth_sha256_hash(num_hashes)),
+            "output of the primary circuit doesn't match the native SHA256 chain"
+        );
+        assert_eq!(
+            zn_secondary,
+            bytes_to_secondary_field(&gen_nth_sha256_hash(2 * num_hashes)),
+            "output of the secondary circuit doesn't match the native SHA256 chain"
+        );
+    };
+
+    let timer_verify_snark = start_timer!(|| "Verify RecursiveSNARK");
+    let zn = record.time("verify", || {
+        recursive_snark
+            .verify(&pp, num_steps, z0_primary.clone(), z0_secondary.clone())
+            .expect("RecursiveSNARK verification failed")
+    });
+    end_timer!(timer_verify_snark);
+    check_outputs(zn);
+
+    let (pk, vk) = record.time("compress_setup", || {
+        CompressedSNARK::<_, _, _, _, S1, S2>::setup(&pp)
+            .expect("failed to create the Spartan keys")
+    });
+
+    let timer_gen_compressed_snark =
+        start_timer!(|| "Generate a CompressedSNARK using Spartan with IPA-PC");
+    let compressed_snark = record.time("compress", || {
+        CompressedSNARK::<_, _, _, _, S1, S2>::prove(&pp, &pk, &recursive_snark)
+            .expect("failed to create the CompressedSNARK")
+    });
+    end_timer!(timer_gen_compressed_snark);
+
+    let timer_verify_compressed_snark = start_timer!(|| "Verify CompressedSNARK");
+    let zn = record.time("verify_compressed", || {
+        compressed_snark
+            .verify(&vk, num_steps, z0_primary, z0_secondary)
+            .expect("CompressedSNARK verification failed")
+    });
+    end_timer!(timer_verify_compressed_snark);
+    check_outputs(zn);
+
+    record.proof_size_bytes = Some(serialized_size(&compressed_snark));
+}
@@ -1,14 +1,520 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+//! Shared harness for the Nova benchmarks.
+//!
+//! Every workload implements [`NovaBenchmark`], which describes where its Circom
+//! artifacts live and which inputs each fold takes. The provided methods then take
+//! care of loading the r1cs, creating public params, proving, verifying and
+//! compressing, so the examples only have to pick a workload and call [`run`].
+
+use std::{
+    collections::HashMap,
+    env::current_dir,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use ark_std::{end_timer, start_timer};
+use ff::{Field, PrimeField};
+use nova_bench::report::BenchRecord;
+use nova_scotia::{
+    circom::{
+        circuit::{CircomCircuit, R1CS},
+        reader::{generate_witness_from_bin, generate_witness_from_wasm, load_r1cs},
+    },
+    create_public_params, create_public_params_par, FileLocation, F1, F2, G1, G2, S1, S2,
+};
+use nova_snark::{
+    parallel_prover::{FoldInput, NovaTreeNode},
+    traits::{circuit::TrivialTestCircuit, Group},
+    CompressedSNARK, ProverKey, PublicParams, RecursiveSNARK, VerifierKey,
+};
+use num_bigint::{BigInt, Sign};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    cache::ParamsCache,
+    proof::{NovaProof, ProofFile},
+};
+
+pub mod bitcoin;
+pub mod cache;
+pub mod cli;
+#[cfg(feature = "dual")]
+pub mod dual;
+pub mod proof;
+pub mod sha256;
+
+pub type C1 = CircomCircuit<F1>;
+pub type C2 = TrivialTestCircuit<F2>;
+
+pub type NovaParams = PublicParams<G1, G2, C1, C2>;
+pub type NovaParParams = nova_snark::parallel_prover::PublicParams<G1, G2, C1, C2>;
+pub type NovaRecursiveSNARK = RecursiveSNARK<G1, G2, C1, C2>;
+pub type NovaCompressedSNARK = CompressedSNARK<G1, G2, C1, C2, S1, S2>;
+pub type NovaProverKey = ProverKey<G1, G2, C1, C2, S1, S2>;
+pub type NovaVerifierKey = VerifierKey<G1, G2, C1, C2, S1, S2>;
+pub type NovaTreeRoot = NovaTreeNode<G1, G2, C1, C2>;
+
+/// Private inputs of a single fold, keyed by Circom signal name.
+pub type PrivateInput = HashMap<String, Value>;
+
+/// Input of the Circom witness generator for one step.
+#[derive(Serialize)]
+struct CircomInput {
+    step_in: Vec<String>,
+    #[serde(flatten)]
+    extra: PrivateInput,
+}
+
+/// Time spent in each step of the sequential prover.
+#[derive(Clone, Debug, Default)]
+pub struct StepTimings {
+    /// Running the Circom witness generator.
+    pub witness: Vec<Duration>,
+    /// `RecursiveSNARK::prove_step`, i.e. the folding itself.
+    pub fold: Vec<Duration>,
+    /// Everything else: serializing the inputs, building the circuit, reading `z_{i+1}`.
+    pub overhead: Vec<Duration>,
+}
+
+/// How the witness of each step is generated from the Circom circuit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum WitnessGenerator {
+    /// Native binary built from the `<name>_cpp` output of Circom (doesn't work on M1).
+    Cpp,
+    /// `<name>_js/<name>.wasm`, run through node.
+    Wasm,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl WitnessGenerator {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WitnessGenerator::Cpp => "cpp",
+            WitnessGenerator::Wasm => "wasm",
+        }
+    }
+
+    /// Location of the witness generator Circom emits for `name` in `dir`.
+    pub fn path(&self, dir: &Path, name: &str) -> PathBuf {
+        match self {
+            WitnessGenerator::Cpp => dir.join(format!("{name}_cpp")).join(name),
+            WitnessGenerator::Wasm => dir.join(format!("{name}_js")).join(format!("{name}.wasm")),
+        }
+    }
+}
+
+/// A workload that is folded with Nova, one Circom step circuit per fold.
+pub trait NovaBenchmark {
+    /// Compiled `.r1cs` of the step circuit.
+    fn r1cs_file(&self) -> PathBuf;
+
+    /// Witness generator of the step circuit, see [`WitnessGenerator`].
+    fn witness_generator_file(&self) -> PathBuf;
+
+    /// Public input `z_0` of the first fold.
+    fn start_public_input(&self) -> Vec<F1>;
+
+    /// Private inputs of every fold, in order. Its length is the number of folds.
+    fn private_inputs(&self) -> Vec<PrivateInput>;
+
+    /// Public input `z_i` of every fold `i`, computed natively. The parallel prover
+    /// needs them upfront since the folds aren't done one after the other.
+    fn step_public_inputs(&self) -> Vec<Vec<F1>>;
+
+    /// Checks the final `z_n` against a native computation, panicking on mismatch.
+    /// Workloads without a native reference keep the default, which accepts anything.
+    fn check_output(&self, _zn: &[F1]) {}
+
+    fn load_circuit(&self) -> R1CS<F1> {
+        load_r1cs(&FileLocation::PathBuf(self.r1cs_file()))
+    }
+
+    fn setup(&self, r1cs: &R1CS<F1>) -> NovaParams {
+        create_public_params(r1cs.clone())
+    }
+
+    /// Generates the witness of every step and folds it, timing both separately.
+    ///
+    /// This is `nova_scotia::create_recursive_circuit`, but interleaved: the witness of
+    /// step `i + 1` is generated after step `i` is folded.
+    fn prove(&self, r1cs: R1CS<F1>, pp: &NovaParams) -> (NovaRecursiveSNARK, StepTimings) {
+        let witness_generator_file = self.witness_generator_file();
+        let witness_generator_output = current_dir().unwrap().join("circom_witness.wtns");
+        let is_wasm = witness_generator_file.extension().unwrap_or_default() == "wasm";
+        let z0_primary = self.start_public_input();
+
+        let mut timings = StepTimings::default();
+        let mut recursive_snark = None;
+        let mut z_i = z0_primary.clone();
+
+        for private_input in self.private_inputs() {
+            let step_start = Instant::now();
+            let input = CircomInput {
+                step_in: z_i.iter().map(field_to_decimal).collect(),
+                extra: private_input,
+            };
+            let input_json = serde_json::to_string(&input).unwrap();
+
+            let start = Instant::now();
+            let witness = if is_wasm {
+                generate_witness_from_wasm::<F1>(
+                    &FileLocation::PathBuf(witness_generator_file.clone()),
+                    &input_json,
+                    &witness_generator_output,
+                )
+            } else {
+                generate_witness_from_bin::<F1>(
+                    &witness_generator_file,
+                    &input_json,
+                    &witness_generator_output,
+                )
+            };
+            let witness_time = start.elapsed();
+
+            let circuit = CircomCircuit {
+                r1cs: r1cs.clone(),
+                witness: Some(witness),
+            };
+            z_i = circuit.get_public_outputs();
+
+            let start = Instant::now();
+            recursive_snark = Some(
+                NovaRecursiveSNARK::prove_step(
+                    pp,
+                    recursive_snark,
+                    circuit,
+                    C2::default(),
+                    z0_primary.clone(),
+                    z0_secondary(),
+                )
+                .expect("failed to fold step"),
+            );
+            let fold_time = start.elapsed();
+
+            timings.witness.push(witness_time);
+            timings.fold.push(fold_time);
+            timings
+                .overhead
+                .push(step_start.elapsed() - witness_time - fold_time);
+        }
+
+        (
+            recursive_snark.expect("at least one step is needed"),
+            timings,
+        )
+    }
+
+    /// Verifies `num_steps` folds of `recursive_snark` and returns the final `z_n`.
+    fn verify(
+        &self,
+        pp: &NovaParams,
+        recursive_snark: &NovaRecursiveSNARK,
+        num_steps: usize,
+    ) -> Vec<F1> {
+        let (zn_primary, _) = recursive_snark
+            .verify(pp, num_steps, self.start_public_input(), z0_secondary())
+            .expect("RecursiveSNARK verification failed");
+        zn_primary
+    }
+
+    /// Spartan (with IPA-PC) prover and verifier keys for [`NovaBenchmark::compress`].
+    fn compress_setup(&self, pp: &NovaParams) -> (NovaProverKey, NovaVerifierKey) {
+        NovaCompressedSNARK::setup(pp).expect("failed to create the Spartan keys")
+    }
+
+    /// Compresses `recursive_snark` with Spartan using IPA-PC.
+    fn compress(
+        &self,
+        pp: &NovaParams,
+        pk: &NovaProverKey,
+        recursive_snark: &NovaRecursiveSNARK,
+    ) -> NovaCompressedSNARK {
+        NovaCompressedSNARK::prove(pp, pk, recursive_snark)
+            .expect("failed to create the CompressedSNARK")
+    }
+
+    fn verify_compressed(
+        &self,
+        vk: &NovaVerifierKey,
+        compressed_snark: &NovaCompressedSNARK,
+        num_steps: usize,
+    ) -> Vec<F1> {
+        let (zn_primary, _) = compressed_snark
+            .verify(vk, num_steps, self.start_public_input(), z0_secondary())
+            .expect("CompressedSNARK verification failed");
+        zn_primary
+    }
+
+    fn setup_par(&self, r1cs: &R1CS<F1>) -> NovaParParams {
+        create_public_params_par(r1cs.clone())
+    }
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    /// Generates the witness of every fold from its own `z_i`, so that the folds link up.
+    fn prepare_folds(&self, r1cs: &R1CS<F1>) -> Vec<FoldInput<G1>> {
+        let private_inputs = self.private_inputs();
+        let step_public_inputs = self.step_public_inputs();
+        assert_eq!(private_inputs.len(), step_public_inputs.len());
+
+        private_inputs
+            .into_iter()
+            .zip(step_public_inputs)
+            .flat_map(|(private_input, z_i)| {
+                nova_scotia::prepare_folds(
+                    FileLocation::PathBuf(self.witness_generator_file()),
+                    r1cs.clone(),
+                    vec![private_input],
+                    1,
+                    z_i,
+                )
+            })
+            .collect()
     }
+
+    /// Folds `folds` as a tree and returns its root, which covers all the steps.
+    fn prove_par(
+        &self,
+        r1cs: R1CS<F1>,
+        pp: NovaParParams,
+        folds: Vec<FoldInput<G1>>,
+    ) -> NovaTreeRoot {
+        let primary_circuit = CircomCircuit {
+            r1cs,
+            witness: None,
+        };
+        let secondary_circuit = C2::default();
+
+        nova_snark::parallel_prover::par_digest_folds(pp, folds, primary_circuit, secondary_circuit)
+            .expect("failed to fold the tree")
+    }
+
+    /// Verifies that the root of the tree covers `num_steps` folds starting from `z_0`
+    /// and returns the final `z_n`, like [`NovaBenchmark::verify`] does.
+    fn verify_par(&self, pp: &NovaParParams, root: &NovaTreeRoot, num_steps: usize) -> Vec<F1> {
+        let (zn_primary, _) = root
+            .verify(pp, num_steps, self.start_public_input(), z0_secondary())
+            .expect("NovaTreeNode verification failed");
+        zn_primary
+    }
+}
+
+/// Public input of the trivial secondary circuit.
+pub fn z0_secondary() -> Vec<F2> {
+    vec![<G2 as Group>::Scalar::zero()]
+}
+
+/// Decimal representation of `x`, as the Circom witness generators read it.
+fn field_to_decimal(x: &F1) -> String {
+    BigInt::from_bytes_le(Sign::Plus, x.to_repr().as_ref()).to_str_radix(10)
+}
+
+/// Maps every byte to its own field element, as the Circom circuits expect.
+pub fn bytes_to_field(bytes: &[u8]) -> Vec<F1> {
+    bytes.iter().map(|&x| F1::from(x as u64)).collect()
+}
+
+/// Inverse of [`bytes_to_field`], `None` if an element doesn't fit in a byte.
+pub fn field_to_bytes(elements: &[F1]) -> Option<Vec<u8>> {
+    elements
+        .iter()
+        .map(|x| {
+            let repr = x.to_repr();
+            let (byte, rest) = repr.as_ref().split_first().unwrap();
+            rest.iter().all(|&b| b == 0).then_some(*byte)
+        })
+        .collect()
+}
+
+pub fn print_shape(num_constraints: (usize, usize), num_variables: (usize, usize)) {
+    println!(
+        "Number of constraints per step (primary circuit): {}",
+        num_constraints.0
+    );
+    println!(
+        "Number of constraints per step (secondary circuit): {}",
+        num_constraints.1
+    );
+
+    println!(
+        "Number of variables per step (primary circuit): {}",
+        num_variables.0
+    );
+    println!(
+        "Number of variables per step (secondary circuit): {}",
+        num_variables.1
+    );
+}
+
+/// Knobs of [`run`] and [`run_par`].
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Compress the `RecursiveSNARK` with Spartan and verify it (sequential only).
+    pub compress: bool,
+    /// Reuse the public params and Spartan keys stored here by earlier runs.
+    pub params_cache: Option<ParamsCache>,
+    /// Write the final proof and its verifying key here, see [`proof`].
+    pub proof_dir: Option<PathBuf>,
+}
+
+impl RunOptions {
+    /// Loads entry `name` from the params cache if there is one, otherwise or on a miss
+    /// calls `create`. Also returns whether it was a cache hit, `None` without a cache.
+    fn cached<T: Serialize + DeserializeOwned>(
+        &self,
+        r1cs_file: &Path,
+        name: &str,
+        create: impl FnOnce() -> T,
+    ) -> (T, Option<bool>) {
+        match &self.params_cache {
+            Some(cache) => {
+                let (value, hit) = cache.load_or_create(r1cs_file, name, create);
+                (value, Some(hit))
+            }
+            None => (create(), None),
+        }
+    }
+}
+
+/// Records a cache hit or miss of entry `name` as param `<name>_cache`.
+fn set_cache_param(record: &mut BenchRecord, name: &str, hit: Option<bool>) {
+    if let Some(hit) = hit {
+        record.set_param(&format!("{name}_cache"), if hit { "hit" } else { "miss" });
+    }
+}
+
+/// Runs the sequential pipeline: setup, prove, verify and, if set in `options`,
+/// compress and verify the compressed SNARK. Phases, circuit shape and proof size
+/// go into `record`.
+pub fn run<B: NovaBenchmark>(bench: &B, options: &RunOptions, record: &mut BenchRecord) {
+    let r1cs = bench.load_circuit();
+    let r1cs_file = bench.r1cs_file();
+    let num_steps = bench.private_inputs().len();
+
+    let (pp, hit) = record.time("setup", || {
+        options.cached(&r1cs_file, "pp", || bench.setup(&r1cs))
+    });
+    set_cache_param(record, "pp", hit);
+    print_shape(pp.num_constraints(), pp.num_variables());
+    record.constraints = Some(pp.num_constraints().0);
+    record.variables = Some(pp.num_variables().0);
+
+    let timer_create_proof = start_timer!(|| "Create RecursiveSNARK");
+    let (recursive_snark, step_timings) = record.time("prove", || bench.prove(r1cs, &pp));
+    end_timer!(timer_create_proof);
+    print_step_timings(&step_timings);
+    record.add_step_stats("witness", &step_timings.witness);
+    record.add_step_stats("fold", &step_timings.fold);
+    record.add_step_stats("overhead", &step_timings.overhead);
+
+    let timer_verify_snark = start_timer!(|| "Verify RecursiveSNARK");
+    let zn = record.time("verify", || bench.verify(&pp, &recursive_snark, num_steps));
+    end_timer!(timer_verify_snark);
+    bench.check_output(&zn);
+
+    if !options.compress {
+        record.proof_size_bytes = Some(serialized_size(&recursive_snark));
+        if let Some(dir) = &options.proof_dir {
+            let proof = NovaProof::Recursive(Box::new(recursive_snark));
+            save_proof(bench, dir, num_steps, proof, &pp, record);
+        }
+        return;
+    }
+
+    let ((pk, vk), hit) = record.time("compress_setup", || {
+        options.cached(&r1cs_file, "spartan_keys", || bench.compress_setup(&pp))
+    });
+    set_cache_param(record, "spartan_keys", hit);
+
+    let timer_gen_compressed_snark =
+        start_timer!(|| "Generate a CompressedSNARK using Spartan with IPA-PC");
+    let compressed_snark = record.time("compress", || bench.compress(&pp, &pk, &recursive_snark));
+    end_timer!(timer_gen_compressed_snark);
+
+    let timer_verify_compressed_snark = start_timer!(|| "Verify CompressedSNARK");
+    let zn = record.time("verify_compressed", || {
+        bench.verify_compressed(&vk, &compressed_snark, num_steps)
+    });
+    end_timer!(timer_verify_compressed_snark);
+    bench.check_output(&zn);
+
+    record.proof_size_bytes = Some(serialized_size(&compressed_snark));
+    if let Some(dir) = &options.proof_dir {
+        let proof = NovaProof::Compressed(Box::new(compressed_snark));
+        save_proof(bench, dir, num_steps, proof, &vk, record);
+    }
+}
+
+/// Runs the parallel prover on the same workload and verifies the root of the tree.
+///
+/// The tree root isn't a `RecursiveSNARK`, so it can't be compressed with Spartan yet.
+pub fn run_par<B: NovaBenchmark>(bench: &B, options: &RunOptions, record: &mut BenchRecord) {
+    let r1cs = bench.load_circuit();
+    let r1cs_file = bench.r1cs_file();
+    let num_steps = bench.private_inputs().len();
+
+    let (pp, hit) = record.time("setup", || {
+        options.cached(&r1cs_file, "pp_par", || bench.setup_par(&r1cs))
+    });
+    set_cache_param(record, "pp_par", hit);
+    print_shape(pp.num_constraints(), pp.num_variables());
+    record.constraints = Some(pp.num_constraints().0);
+    record.variables = Some(pp.num_variables().0);
+    // `par_digest_folds` takes the params by value, keep a copy for the verifier.
+    let pp_verifier = pp.clone();
+
+    let proving_time = start_timer!(|| "Proving time");
+    let folds = record.time("witness", || bench.prepare_folds(&r1cs));
+    let root = record.time("prove", || bench.prove_par(r1cs, pp, folds));
+    end_timer!(proving_time);
+
+    let timer_verify_snark = start_timer!(|| "Verify NovaTreeNode");
+    let zn = record.time("verify", || {
+        bench.verify_par(&pp_verifier, &root, num_steps)
+    });
+    end_timer!(timer_verify_snark);
+    bench.check_output(&zn);
+
+    record.proof_size_bytes = Some(serialized_size(&root));
+    if let Some(dir) = &options.proof_dir {
+        save_proof(
+            bench,
+            dir,
+            num_steps,
+            NovaProof::Tree(Box::new(root)),
+            &pp_verifier,
+            record,
+        );
+    }
+}
+
+fn save_proof<B: NovaBenchmark, V: Serialize>(
+    bench: &B,
+    dir: &Path,
+    num_steps: usize,
+    proof: NovaProof,
+    vk: &V,
+    record: &BenchRecord,
+) {
+    let proof_file = ProofFile {
+        num_steps,
+        z0: bench.start_public_input(),
+        proof,
+    };
+    proof_file
+        .write(dir, vk, record)
+        .unwrap_or_else(|e| panic!("failed to write the proof to {}: {e}", dir.display()));
+    println!("Wrote the proof to {}", dir.display());
+}
+
+fn print_step_timings(timings: &StepTimings) {
+    let sum = |durations: &[Duration]| durations.iter().sum::<Duration>();
+    println!(
+        "Step sum (prove_step only): {:?}, witness generation: {:?}, overhead: {:?}",
+        sum(&timings.fold),
+        sum(&timings.witness),
+        sum(&timings.overhead)
+    );
+}
+
+fn serialized_size<T: Serialize>(value: &T) -> usize {
+    bincode::serialized_size(value).expect("failed to serialize the proof") as usize
 }
@@ -0,0 +1,109 @@
+//! Nova proofs on disk, see `nova_bench::proof` for the layout of a proof directory.
+//!
+//! `proof.bin` is a bincode [`ProofFile`]. What goes into `vk.bin` depends on the
+//! kind of proof: a `RecursiveSNARK` and the root of the parallel tree are checked
+//! against the public params, a `CompressedSNARK` against its Spartan verifier key.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use nova_bench::{
+    proof::{proof_path, vk_path, ProofMeta},
+    report::BenchRecord,
+};
+use nova_scotia::F1;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    z0_secondary, NovaCompressedSNARK, NovaParParams, NovaParams, NovaRecursiveSNARK, NovaTreeRoot,
+    NovaVerifierKey,
+};
+
+#[derive(Serialize, Deserialize)]
+pub enum NovaProof {
+    Recursive(Box<NovaRecursiveSNARK>),
+    Compressed(Box<NovaCompressedSNARK>),
+    Tree(Box<NovaTreeRoot>),
+}
+
+/// Everything the verifier needs to know about a proof besides its verifying key.
+#[derive(Serialize, Deserialize)]
+pub struct ProofFile {
+    pub num_steps: usize,
+    /// Public input of the first fold.
+    pub z0: Vec<F1>,
+    pub proof: NovaProof,
+}
+
+type BoxError = Box<dyn std::error::Error>;
+
+impl ProofFile {
+    /// Writes the proof and `vk` to `dir`, along with the metadata of `record`.
+    /// `vk` has to match the kind of proof, see the module docs.
+    pub fn write<V: Serialize>(
+        &self,
+        dir: &Path,
+        vk: &V,
+        record: &BenchRecord,
+    ) -> Result<(), BoxError> {
+        ProofMeta::from_record(record).write(dir)?;
+        write_bincode(&proof_path(dir), self)?;
+        write_bincode(&vk_path(dir), vk)?;
+        Ok(())
+    }
+
+    pub fn read(dir: &Path) -> Result<Self, BoxError> {
+        read_bincode(&proof_path(dir))
+    }
+
+    /// Loads the verifying key of this kind of proof from `dir`.
+    pub fn read_vk(&self, dir: &Path) -> Result<VerifyingKey, BoxError> {
+        let path = vk_path(dir);
+        Ok(match self.proof {
+            NovaProof::Recursive(_) => VerifyingKey::Params(read_bincode(&path)?),
+            NovaProof::Compressed(_) => VerifyingKey::Spartan(read_bincode(&path)?),
+            NovaProof::Tree(_) => VerifyingKey::ParParams(read_bincode(&path)?),
+        })
+    }
+
+    /// Verifies the proof and returns the final `z_n`.
+    pub fn verify(&self, vk: &VerifyingKey) -> Result<Vec<F1>, String> {
+        let z0 = self.z0.clone();
+        let (zn_primary, _) = match (&self.proof, vk) {
+            (NovaProof::Recursive(snark), VerifyingKey::Params(pp)) => snark
+                .verify(pp, self.num_steps, z0, z0_secondary())
+                .map_err(|e| format!("{e:?}"))?,
+            (NovaProof::Compressed(snark), VerifyingKey::Spartan(vk)) => snark
+                .verify(vk, self.num_steps, z0, z0_secondary())
+                .map_err(|e| format!("{e:?}"))?,
+            (NovaProof::Tree(root), VerifyingKey::ParParams(pp)) => root
+                .verify(pp, self.num_steps, z0, z0_secondary())
+                .map_err(|e| format!("{e:?}"))?,
+            _ => return Err("verifying key doesn't match the proof".to_string()),
+        };
+        Ok(zn_primary)
+    }
+}
+
+/// Contents of `vk.bin`.
+pub enum VerifyingKey {
+    Params(Box<NovaParams>),
+    ParParams(Box<NovaParParams>),
+    Spartan(Box<NovaVerifierKey>),
+}
+
+fn write_bincode<T: Serialize>(path: &Path, value: &T) -> Result<(), BoxError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    bincode::serialize_into(&mut writer, value)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_bincode<T: DeserializeOwned>(path: &Path) -> Result<T, BoxError> {
+    let file = File::open(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+    Ok(bincode::deserialize_from(BufReader::new(file))?)
+}
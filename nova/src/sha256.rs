@@ -0,0 +1,210 @@
+//! Recursive SHA256 hashing, i.e. computations of the form `h(h(h(x)))`, with a
+//! batch of hashes done inside every fold.
+
+use std::{env::current_dir, path::PathBuf, process::Command};
+
+use nova_bench::{report::BenchRecord, sha256::sha256_chain_digest};
+use nova_scotia::F1;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::{bytes_to_field, field_to_bytes, NovaBenchmark, PrivateInput, WitnessGenerator};
+
+const CIRCUIT_NAME: &str = "sha256_test_nova";
+const CHECKED_CIRCUIT_NAME: &str = "sha256_test_nova_checked";
+
+pub struct Sha256Chain {
+    /// Number of folds.
+    pub num_steps: usize,
+    /// Number of hashes done inside each fold, `d` in the README.
+    pub depth_per_fold: usize,
+    pub witness: WitnessGenerator,
+    /// Use `sha256_test_nova_checked.circom`, which constrains the private `in` and
+    /// `hash` inputs of every fold instead of ignoring them.
+    pub checked: bool,
+}
+
+impl Sha256Chain {
+    pub fn new(
+        num_steps: usize,
+        depth_per_fold: usize,
+        witness: WitnessGenerator,
+        checked: bool,
+    ) -> Self {
+        Self {
+            num_steps,
+            depth_per_fold,
+            witness,
+            checked,
+        }
+    }
+
+    /// Total number of hashes in the chain, `k` in the README.
+    pub fn num_hashes(&self) -> usize {
+        self.num_steps * self.depth_per_fold
+    }
+
+    /// Empty result record describing this run, see [`crate::run`].
+    pub fn record(&self, mode: &str) -> BenchRecord {
+        let mut record = BenchRecord::new("nova", mode, self.num_hashes());
+        record.d = Some(self.depth_per_fold);
+        record.set_param("steps", self.num_steps);
+        record.set_param("witness", self.witness.name());
+        record.set_param("checked", self.checked);
+        record
+    }
+
+    /// Renders and compiles `Main(depth_per_fold)` and its [`WitnessGenerator`] unless
+    /// both were already built. The other generator isn't built, nor looked for.
+    pub fn compile(&self) {
+        if self.r1cs_file().exists() && self.witness_generator_file().exists() {
+            return;
+        }
+        println!(
+            "Compiling the step circuit for depth_per_fold = {}",
+            self.depth_per_fold
+        );
+        self.run_compile_script("vesta");
+    }
+
+    /// Compiles the step circuit over circom's `pallas`, the field of the secondary
+    /// circuit of [`crate::dual`], unless it was already built.
+    #[cfg(feature = "dual")]
+    pub fn compile_secondary(&self) {
+        if self.secondary_r1cs_file().exists() {
+            return;
+        }
+        println!(
+            "Compiling the secondary step circuit for depth_per_fold = {}",
+            self.depth_per_fold
+        );
+        self.run_compile_script("pallas");
+    }
+
+    /// Compiled `.r1cs` of the step circuit over the field of the secondary circuit.
+    #[cfg(feature = "dual")]
+    pub fn secondary_r1cs_file(&self) -> PathBuf {
+        self.circuit_dir()
+            .join("pallas")
+            .join(format!("{}.r1cs", self.circuit_name()))
+    }
+
+    fn run_compile_script(&self, prime: &str) {
+        let status = Command::new("bash")
+            .arg("examples/sha256/circom/compile.sh")
+            .arg(self.depth_per_fold.to_string())
+            .arg(self.circuit_name())
+            .arg(self.witness.name())
+            .arg(prime)
+            .status()
+            .expect("failed to execute process");
+        assert!(status.success(), "failed to compile the step circuit");
+    }
+
+    fn circuit_name(&self) -> &'static str {
+        if self.checked {
+            CHECKED_CIRCUIT_NAME
+        } else {
+            CIRCUIT_NAME
+        }
+    }
+
+    fn circuit_dir(&self) -> PathBuf {
+        current_dir()
+            .unwrap()
+            .join("examples/sha256/circom/build")
+            .join(format!("d{}", self.depth_per_fold))
+    }
+}
+
+/// Returns the first `n` elements of the chain `0^32, h(0^32), h(h(0^32)), ...`.
+pub fn sha256_chain(n: usize) -> Vec<Vec<u8>> {
+    let mut hash = vec![0; 32];
+    let mut chain = Vec::with_capacity(n);
+    for _ in 0..n {
+        let new_hash = Sha256::digest(&hash).to_vec();
+        chain.push(std::mem::replace(&mut hash, new_hash));
+    }
+    chain
+}
+
+/// The `n`-th element of [`sha256_chain`], i.e. the output of `n` recursive hashes.
+pub fn gen_nth_sha256_hash(n: usize) -> Vec<u8> {
+    sha256_chain_digest([0; 32], n).to_vec()
+}
+
+impl NovaBenchmark for Sha256Chain {
+    fn r1cs_file(&self) -> PathBuf {
+        self.circuit_dir()
+            .join(format!("{}.r1cs", self.circuit_name()))
+    }
+
+    fn witness_generator_file(&self) -> PathBuf {
+        self.witness.path(&self.circuit_dir(), self.circuit_name())
+    }
+
+    fn start_public_input(&self) -> Vec<F1> {
+        bytes_to_field(&[0; 32])
+    }
+
+    fn check_output(&self, zn: &[F1]) {
+        let expected = gen_nth_sha256_hash(self.num_hashes());
+        let output = field_to_bytes(zn).expect("step_out is not a byte array");
+        assert_eq!(
+            output,
+            expected,
+            "output of {} recursive hashes doesn't match the native SHA256 chain",
+            self.num_hashes()
+        );
+    }
+
+    /// `z_i = h^{i*d}(0)`.
+    fn step_public_inputs(&self) -> Vec<Vec<F1>> {
+        sha256_chain(self.num_hashes())
+            .iter()
+            .step_by(self.depth_per_fold)
+            .map(|hash| bytes_to_field(hash))
+            .collect()
+    }
+
+    /// Fold `i` gets `h^{i*d}(0)` as `in` and, for the checked circuit, `h^{(i+1)*d}(0)`
+    /// as `hash`.
+    fn private_inputs(&self) -> Vec<PrivateInput> {
+        let chain = sha256_chain(self.num_hashes() + 1);
+        (0..self.num_steps)
+            .map(|i| {
+                let mut private_input = PrivateInput::new();
+                let start = i * self.depth_per_fold;
+                private_input.insert("in".to_string(), json!(chain[start]));
+                if self.checked {
+                    private_input.insert(
+                        "hash".to_string(),
+                        json!(chain[start + self.depth_per_fold]),
+                    );
+                }
+                private_input
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_starts_from_zero_preimage() {
+        let chain = sha256_chain(3);
+        assert_eq!(chain[0], vec![0; 32]);
+        // See `examples/sha256/circom/sha256_bytearray.py`
+        assert_eq!(chain[1][..4], [102, 104, 122, 173]);
+        assert_eq!(chain[2], gen_nth_sha256_hash(2));
+    }
+
+    #[test]
+    fn bytes_roundtrip_through_field() {
+        let hash = gen_nth_sha256_hash(5);
+        assert_eq!(field_to_bytes(&bytes_to_field(&hash)), Some(hash));
+        assert_eq!(field_to_bytes(&[F1::from(256)]), None);
+    }
+}